@@ -20,7 +20,80 @@ fn strip_underscore(input: &str) -> String {
     result
 }
 
-#[proc_macro_derive(DBData)]
+/// Entero con signo más chico que puede representar al entero sin signo `ty`.
+fn signed_equivalent(ty: &syn::Type) -> Option<proc_macro2::TokenStream> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    match segment.ident.to_string().as_str() {
+        "u8" => Some(quote! { i16 }),
+        "u16" => Some(quote! { i32 }),
+        "u32" => Some(quote! { i64 }),
+        _ => None,
+    }
+}
+
+fn is_decimal(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "BigDecimal"),
+        _ => false,
+    }
+}
+
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Tipo de la columna según el atributo `#[dbdata(...)]` del campo, o `TipoColumna::Libre` si no
+/// tiene.
+fn column_type(field: &syn::Field) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let mut column = None;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("dbdata")) {
+        attr.parse_nested_meta(|meta| {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            if meta.path.is_ident("varchar") || meta.path.is_ident("char") {
+                let length: syn::LitInt = content.parse()?;
+                let length = length.base10_parse::<usize>()?;
+                column = Some(quote! { dbdata::TipoColumna::Texto(#length) });
+            } else if meta.path.is_ident("decimal") {
+                let precision: syn::LitInt = content.parse()?;
+                content.parse::<syn::Token![,]>()?;
+                let scale: syn::LitInt = content.parse()?;
+                let precision = precision.base10_parse::<u32>()?;
+                let scale = scale.base10_parse::<u32>()?;
+                if scale > precision {
+                    return Err(meta.error("la escala no puede ser mayor que la precisión"));
+                }
+                column = Some(quote! { dbdata::TipoColumna::Decimal(#precision, #scale) });
+            } else {
+                return Err(meta.error("se esperaba varchar(n), char(n) o decimal(p, s)"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(column)
+}
+
+#[proc_macro_derive(DBData, attributes(dbdata))]
 pub fn dbdata_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
     impl_dbdata_macro(&ast)
@@ -52,21 +125,65 @@ fn impl_dbdata_macro(ast: &syn::DeriveInput) -> TokenStream {
         .trim_end_matches(',')
         .to_string();
 
+    // Los enteros sin signo se envían como enteros con signo porque Postgres no los tiene, y los
+    // decimales con el tipo que indique el dialecto.
     let mut field_accessors = quote! {};
-    for f in &fields {
+    let mut tsv_fields = quote! {};
+    let mut column_values = quote! {};
+    let mut bounds = quote! {};
+    let mut column_types = quote! {};
+    let mut adjustments = quote! {};
+    for (i, f) in fields.iter().enumerate() {
+        let ty = &f.ty;
+        let column = match column_type(f) {
+            Ok(column) => column,
+            Err(error) => return error.to_compile_error().into(),
+        };
         let f = f.ident.clone().unwrap();
+        if let Some(column) = column {
+            column_types = quote! { #column_types #column, };
+            adjustments = quote! {
+                #adjustments
+                dbdata::Ajustable::ajustar(&mut self.#f, #column);
+            };
+        } else {
+            column_types = quote! { #column_types dbdata::TipoColumna::Libre, };
+        }
+        let (value, bound) = if let Some(signed) = signed_equivalent(ty) {
+            (quote! { #signed::from(fila.#f) }, Some(quote! { #signed }))
+        } else if let Some(signed) = option_inner(ty).and_then(signed_equivalent) {
+            (
+                quote! { fila.#f.map(#signed::from) },
+                Some(quote! { Option<#signed> }),
+            )
+        } else if is_decimal(ty) {
+            (quote! { DB::decimal(&fila.#f) }, None)
+        } else {
+            (quote! { fila.#f.clone() }, Some(quote! { #ty }))
+        };
         field_accessors = quote! {
             #field_accessors
-            .bind(&self.#f)
+            .bind(#value)
+        };
+        if i > 0 {
+            tsv_fields = quote! {
+                #tsv_fields
+                salida.push('\t');
+            };
         }
-    }
-
-    let mut vec_field_accessors = quote! {};
-    for f in &fields {
-        let f = f.ident.clone().unwrap();
-        vec_field_accessors = quote! {
-            #vec_field_accessors
-            .push_bind(t.#f)
+        tsv_fields = quote! {
+            #tsv_fields
+            dbdata::ValorTsv::escribir_tsv(&self.#f, salida);
+        };
+        column_values = quote! {
+            #column_values
+            dbdata::ValorColumna::valor(&self.#f),
+        };
+        if let Some(bound) = bound {
+            bounds = quote! {
+                #bounds
+                #bound: for<'q> sqlx::Encode<'q, DB> + sqlx::Type<DB> + Send,
+            };
         }
     }
 
@@ -75,22 +192,102 @@ fn impl_dbdata_macro(ast: &syn::DeriveInput) -> TokenStream {
         table_name, table_values, empty_fields
     );
 
+    let pg_fields = (1..=fields_ammount)
+        .map(|i| format!("${i}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    let pg_insert_query = format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        table_name, table_values, pg_fields
+    );
+
+    let table = table_name.to_string();
+    let columns: Vec<String> = field_names
+        .iter()
+        .map(|f| strip_underscore(f).to_lowercase())
+        .collect();
+
     let gen = quote! {
-        impl DBData for #table_name {
-            async fn insertar_en_db(&self, pool: &Pool<MySql>) -> Result<(), anyhow::Error> {
-                match sqlx::query(#insert_query)
-                #field_accessors
-                .execute(pool)
-                .await
-                {
-                    Ok(_) => incrementar_contador(INFO).await,
-                    Err(err) => {
-                        notificar_carga(WARN, &err.to_string());
-                        incrementar_contador(WARN).await;
-                    }
+        impl dbdata::Fila for #table_name {
+            const TABLA: &'static str = #table;
+            const COLUMNAS: &'static [&'static str] = &[#(#columns),*];
+            const TIPOS: &'static [dbdata::TipoColumna] = &[#column_types];
+
+            fn valores(&self) -> Vec<dbdata::Valor> {
+                vec![#column_values]
+            }
+
+            fn ajustar(&mut self) {
+                #adjustments
+            }
+        }
+
+        impl dbdata::FilaTsv for #table_name {
+            fn escribir_tsv(&self, salida: &mut String) {
+                #tsv_fields
+                salida.push('\n');
+            }
+        }
+
+        impl<DB> DBData<DB> for #table_name
+        where
+            DB: dbdata::Dialecto,
+            for<'q> <DB as sqlx::database::HasArguments<'q>>::Arguments: sqlx::IntoArguments<'q, DB>,
+            #bounds
+        {
+            async fn insertar_en_db<'c, E>(&self, ejecutor: E) -> Result<(), anyhow::Error>
+            where
+                E: sqlx::Executor<'c, Database = DB>,
+            {
+                let consulta = if DB::PARAMETROS_NUMERADOS {
+                    #pg_insert_query
+                } else {
+                    #insert_query
                 };
+                let fila = self;
+                sqlx::query::<DB>(consulta)
+                    #field_accessors
+                    .execute(ejecutor)
+                    .await?;
                 Ok(())
             }
+
+            async fn insertar_lote_en_db<'c, E>(
+                filas: &[Self],
+                ejecutor: E,
+            ) -> Result<u64, anyhow::Error>
+            where
+                E: sqlx::Executor<'c, Database = DB>,
+            {
+                let (inicio, fin) = DB::INSERTAR_IGNORANDO;
+                let mut valores = Vec::with_capacity(filas.len());
+                for i in 0..filas.len() {
+                    let parametros: Vec<String> = (1..=#fields_ammount)
+                        .map(|j| {
+                            if DB::PARAMETROS_NUMERADOS {
+                                format!("${}", i * #fields_ammount + j)
+                            } else {
+                                "?".to_string()
+                            }
+                        })
+                        .collect();
+                    valores.push(format!("({})", parametros.join(",")));
+                }
+                let sql = format!(
+                    "{} {} ({}) VALUES {}{}",
+                    inicio,
+                    #table,
+                    #table_values,
+                    valores.join(","),
+                    fin
+                );
+                let mut consulta = sqlx::query::<DB>(&sql);
+                for fila in filas {
+                    consulta = consulta #field_accessors;
+                }
+                let resultado = consulta.execute(ejecutor).await?;
+                Ok(DB::filas_afectadas(&resultado))
+            }
         }
     };
     gen.into()
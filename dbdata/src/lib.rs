@@ -1,7 +1,465 @@
-use sqlx::{MySql, Pool};
+use std::fmt::Write;
+
+use sqlx::{
+    database::HasArguments,
+    types::{
+        time::{Date, Time},
+        BigDecimal,
+    },
+    Database, Encode, Executor, IntoArguments, MySql, Pool, Postgres, Sqlite, Type,
+};
+
+/// Diferencias entre los motores de base de datos que afectan a los INSERT generados.
+pub trait Dialecto: Database {
+    /// `true` si los parámetros se escriben `$1, $2, ...` en lugar de `?`.
+    const PARAMETROS_NUMERADOS: bool;
+
+    /// Comienzo y final de un INSERT que descarta las filas que no se pueden insertar, como las
+    /// duplicadas, en lugar de fallar.
+    const INSERTAR_IGNORANDO: (&'static str, &'static str);
+
+    /// Tipo con el que se envían los decimales.
+    type Decimal: for<'q> Encode<'q, Self> + Type<Self> + Send;
+
+    fn decimal(valor: &BigDecimal) -> Self::Decimal;
+
+    /// Filas insertadas, modificadas o borradas por una consulta.
+    fn filas_afectadas(resultado: &Self::QueryResult) -> u64;
+}
+
+impl Dialecto for MySql {
+    const PARAMETROS_NUMERADOS: bool = false;
+    const INSERTAR_IGNORANDO: (&'static str, &'static str) = ("INSERT IGNORE INTO", "");
+    type Decimal = BigDecimal;
+
+    fn decimal(valor: &BigDecimal) -> Self::Decimal {
+        valor.clone()
+    }
+
+    fn filas_afectadas(resultado: &Self::QueryResult) -> u64 {
+        resultado.rows_affected()
+    }
+}
+
+impl Dialecto for Postgres {
+    const PARAMETROS_NUMERADOS: bool = true;
+    // Sólo descarta las filas duplicadas.
+    const INSERTAR_IGNORANDO: (&'static str, &'static str) =
+        ("INSERT INTO", " ON CONFLICT DO NOTHING");
+    type Decimal = BigDecimal;
+
+    fn decimal(valor: &BigDecimal) -> Self::Decimal {
+        valor.clone()
+    }
+
+    fn filas_afectadas(resultado: &Self::QueryResult) -> u64 {
+        resultado.rows_affected()
+    }
+}
+
+impl Dialecto for Sqlite {
+    const PARAMETROS_NUMERADOS: bool = false;
+    const INSERTAR_IGNORANDO: (&'static str, &'static str) = ("INSERT OR IGNORE INTO", "");
+    // SQLite no tiene un tipo decimal, por lo que se guardan como texto.
+    type Decimal = String;
+
+    fn decimal(valor: &BigDecimal) -> Self::Decimal {
+        valor.to_string()
+    }
+
+    fn filas_afectadas(resultado: &Self::QueryResult) -> u64 {
+        resultado.rows_affected()
+    }
+}
 
 #[allow(async_fn_in_trait)]
-/// Trait que representa a un dato que sabe cómo insertarse en una base de datos MySQL.
-pub trait DBData {
-    async fn insertar_en_db(&self, pool: &Pool<MySql>) -> Result<(), anyhow::Error>;
+/// Trait que representa a un dato que sabe cómo insertarse en una base de datos de tipo `DB`, ya
+/// sea a través de un pool, de una conexión o de una transacción.
+pub trait DBData<DB: Database> {
+    async fn insertar_en_db<'c, E>(&self, ejecutor: E) -> Result<(), anyhow::Error>
+    where
+        E: Executor<'c, Database = DB>;
+
+    /// Inserta `filas` con un solo INSERT, descartando las que no se puedan insertar (ver
+    /// [`Dialecto::INSERTAR_IGNORANDO`]). Devuelve la cantidad de filas insertadas.
+    async fn insertar_lote_en_db<'c, E>(filas: &[Self], ejecutor: E) -> Result<u64, anyhow::Error>
+    where
+        E: Executor<'c, Database = DB>,
+        Self: Sized;
+}
+
+/// Valor que se puede escribir en un archivo TSV con el formato por defecto de
+/// `LOAD DATA INFILE` de MySQL.
+pub trait ValorTsv {
+    fn escribir_tsv(&self, salida: &mut String);
+}
+
+/// Escribe `texto` escapando los caracteres especiales de `LOAD DATA INFILE`.
+pub fn escapar_tsv(texto: &str, salida: &mut String) {
+    for c in texto.chars() {
+        match c {
+            '\\' => salida.push_str("\\\\"),
+            '\t' => salida.push_str("\\t"),
+            '\n' => salida.push_str("\\n"),
+            '\r' => salida.push_str("\\r"),
+            '\0' => salida.push_str("\\0"),
+            c => salida.push(c),
+        }
+    }
+}
+
+impl ValorTsv for String {
+    fn escribir_tsv(&self, salida: &mut String) {
+        escapar_tsv(self, salida);
+    }
+}
+
+impl ValorTsv for bool {
+    fn escribir_tsv(&self, salida: &mut String) {
+        salida.push(if *self { '1' } else { '0' });
+    }
+}
+
+macro_rules! valor_tsv_con_display {
+    ($($tipo:ty),*) => {
+        $(impl ValorTsv for $tipo {
+            fn escribir_tsv(&self, salida: &mut String) {
+                let _ = write!(salida, "{self}");
+            }
+        })*
+    };
+}
+
+valor_tsv_con_display!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, BigDecimal);
+
+impl ValorTsv for Date {
+    fn escribir_tsv(&self, salida: &mut String) {
+        let _ = write!(
+            salida,
+            "{:04}-{:02}-{:02}",
+            self.year(),
+            u8::from(self.month()),
+            self.day()
+        );
+    }
+}
+
+impl ValorTsv for Time {
+    fn escribir_tsv(&self, salida: &mut String) {
+        let _ = write!(
+            salida,
+            "{:02}:{:02}:{:02}",
+            self.hour(),
+            self.minute(),
+            self.second()
+        );
+    }
+}
+
+impl<T: ValorTsv> ValorTsv for Option<T> {
+    fn escribir_tsv(&self, salida: &mut String) {
+        match self {
+            Some(valor) => valor.escribir_tsv(salida),
+            None => salida.push_str("\\N"),
+        }
+    }
+}
+
+/// Valor de una columna, independiente del motor de base de datos y del formato en que se
+/// escriba.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Valor {
+    Nulo,
+    Booleano(bool),
+    Entero(i64),
+    Real(f64),
+    Decimal(BigDecimal),
+    Texto(String),
+    Fecha(Date),
+    Hora(Time),
+}
+
+/// Tipo de una columna que se puede convertir en un [`Valor`].
+pub trait ValorColumna {
+    fn valor(&self) -> Valor;
+}
+
+impl ValorColumna for String {
+    fn valor(&self) -> Valor {
+        Valor::Texto(self.clone())
+    }
+}
+
+impl ValorColumna for bool {
+    fn valor(&self) -> Valor {
+        Valor::Booleano(*self)
+    }
+}
+
+macro_rules! valor_columna_entero {
+    ($($tipo:ty),*) => {
+        $(impl ValorColumna for $tipo {
+            fn valor(&self) -> Valor {
+                Valor::Entero(i64::from(*self))
+            }
+        })*
+    };
+}
+
+valor_columna_entero!(u8, u16, u32, i8, i16, i32, i64);
+
+impl ValorColumna for f32 {
+    fn valor(&self) -> Valor {
+        Valor::Real(f64::from(*self))
+    }
+}
+
+impl ValorColumna for f64 {
+    fn valor(&self) -> Valor {
+        Valor::Real(*self)
+    }
+}
+
+impl ValorColumna for BigDecimal {
+    fn valor(&self) -> Valor {
+        Valor::Decimal(self.clone())
+    }
+}
+
+impl ValorColumna for Date {
+    fn valor(&self) -> Valor {
+        Valor::Fecha(*self)
+    }
+}
+
+impl ValorColumna for Time {
+    fn valor(&self) -> Valor {
+        Valor::Hora(*self)
+    }
+}
+
+impl<T: ValorColumna> ValorColumna for Option<T> {
+    fn valor(&self) -> Valor {
+        self.as_ref().map_or(Valor::Nulo, ValorColumna::valor)
+    }
+}
+
+/// Tipo de una columna en la base de datos, cuando restringe los valores más que el tipo de Rust
+/// del campo. Se declara en el campo con `#[dbdata(varchar(n))]`, `#[dbdata(char(n))]` o
+/// `#[dbdata(decimal(p, s))]`. Las columnas `unsigned` se representan con enteros sin signo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipoColumna {
+    /// Sin restricciones más allá del tipo del campo.
+    Libre,
+    /// Texto de a lo sumo esta cantidad de caracteres.
+    Texto(usize),
+    /// Número con esta precisión y escala.
+    Decimal(u32, u32),
+}
+
+/// Valor que se puede modificar para que entre en una columna de tipo [`TipoColumna`].
+pub trait Ajustable {
+    fn ajustar(&mut self, tipo: TipoColumna);
+}
+
+impl Ajustable for String {
+    /// Corta el texto en el largo de la columna, sin dejar espacios al final.
+    fn ajustar(&mut self, tipo: TipoColumna) {
+        if let TipoColumna::Texto(largo) = tipo {
+            if let Some((corte, _)) = self.char_indices().nth(largo) {
+                self.truncate(corte);
+                self.truncate(self.trim_end().len());
+            }
+        }
+    }
+}
+
+impl Ajustable for BigDecimal {
+    /// Redondea el número a la escala de la columna y, si tiene más dígitos enteros que los que
+    /// admite, lo reemplaza por el mayor valor con su signo.
+    fn ajustar(&mut self, tipo: TipoColumna) {
+        if let TipoColumna::Decimal(precision, escala) = tipo {
+            let redondeado = self.round(i64::from(escala));
+            let limite = BigDecimal::new(1.into(), -i64::from(precision - escala));
+            *self = if redondeado.abs() < limite {
+                redondeado
+            } else {
+                let maximo = limite - BigDecimal::new(1.into(), i64::from(escala));
+                if redondeado < BigDecimal::from(0) {
+                    -maximo
+                } else {
+                    maximo
+                }
+            };
+        }
+    }
+}
+
+impl Ajustable for f64 {
+    /// Como el de [`BigDecimal`].
+    fn ajustar(&mut self, tipo: TipoColumna) {
+        if let TipoColumna::Decimal(precision, escala) = tipo {
+            let factor = 10f64.powi(escala as i32);
+            let maximo = 10f64.powi((precision - escala) as i32) - 1.0 / factor;
+            *self = ((*self * factor).round() / factor).clamp(-maximo, maximo);
+        }
+    }
+}
+
+impl<T: Ajustable> Ajustable for Option<T> {
+    fn ajustar(&mut self, tipo: TipoColumna) {
+        if let Some(valor) = self {
+            valor.ajustar(tipo);
+        }
+    }
+}
+
+/// Fila de una tabla.
+pub trait Fila {
+    const TABLA: &'static str;
+    /// Columnas en el orden en que se escriben los valores.
+    const COLUMNAS: &'static [&'static str];
+    /// Tipos de las columnas, en el orden de [`Fila::COLUMNAS`].
+    const TIPOS: &'static [TipoColumna];
+
+    /// Valores de las columnas, en el orden de [`Fila::COLUMNAS`].
+    fn valores(&self) -> Vec<Valor>;
+
+    /// Modifica los valores que no entran en sus columnas según [`Fila::TIPOS`].
+    fn ajustar(&mut self);
+
+    /// Tipo de la columna `columna`, o `None` si la tabla no la tiene.
+    fn tipo_columna(columna: &str) -> Option<TipoColumna>
+    where
+        Self: Sized,
+    {
+        Self::COLUMNAS
+            .iter()
+            .position(|c| *c == columna)
+            .map(|i| Self::TIPOS[i])
+    }
+
+    /// La fila con los valores ajustados, para terminar de construirla.
+    fn ajustada(mut self) -> Self
+    where
+        Self: Sized,
+    {
+        self.ajustar();
+        self
+    }
+}
+
+/// Fila de una tabla que se puede escribir como una línea de un archivo TSV.
+pub trait FilaTsv: Fila {
+    /// Escribe la fila terminada en un salto de línea.
+    fn escribir_tsv(&self, salida: &mut String);
+}
+
+/// Dato que se puede insertar en cualquiera de los motores de [`Conexion`].
+pub trait Insertable: DBData<MySql> + DBData<Postgres> + DBData<Sqlite> + FilaTsv {}
+
+impl<T> Insertable for T where T: DBData<MySql> + DBData<Postgres> + DBData<Sqlite> + FilaTsv {}
+
+/// Conexión con alguno de los motores de base de datos soportados.
+#[derive(Debug, Clone)]
+pub enum Conexion {
+    MySql(Pool<MySql>),
+    Postgres(Pool<Postgres>),
+    Sqlite(Pool<Sqlite>),
+}
+
+impl From<Pool<MySql>> for Conexion {
+    fn from(pool: Pool<MySql>) -> Self {
+        Conexion::MySql(pool)
+    }
+}
+
+impl From<Pool<Postgres>> for Conexion {
+    fn from(pool: Pool<Postgres>) -> Self {
+        Conexion::Postgres(pool)
+    }
+}
+
+impl From<Pool<Sqlite>> for Conexion {
+    fn from(pool: Pool<Sqlite>) -> Self {
+        Conexion::Sqlite(pool)
+    }
+}
+
+impl Conexion {
+    /// Inserta `filas` con un solo INSERT que descarta las que no se puedan insertar. Devuelve la
+    /// cantidad de filas insertadas.
+    pub async fn insertar_lote<T: Insertable>(&self, filas: &[T]) -> Result<u64, anyhow::Error> {
+        match self {
+            Conexion::MySql(pool) => DBData::<MySql>::insertar_lote_en_db(filas, pool).await,
+            Conexion::Postgres(pool) => DBData::<Postgres>::insertar_lote_en_db(filas, pool).await,
+            Conexion::Sqlite(pool) => DBData::<Sqlite>::insertar_lote_en_db(filas, pool).await,
+        }
+    }
+
+    /// Inserta `dato` en la base de datos de la conexión.
+    pub async fn insertar<T: Insertable>(&self, dato: &T) -> Result<(), anyhow::Error> {
+        match self {
+            Conexion::MySql(pool) => DBData::<MySql>::insertar_en_db(dato, pool).await,
+            Conexion::Postgres(pool) => DBData::<Postgres>::insertar_en_db(dato, pool).await,
+            Conexion::Sqlite(pool) => DBData::<Sqlite>::insertar_en_db(dato, pool).await,
+        }
+    }
+
+    /// Ejecuta `consulta`, que no devuelve filas, con `valores` como parámetros. Los parámetros
+    /// se marcan con `?` y se numeran en los motores con [`Dialecto::PARAMETROS_NUMERADOS`];
+    /// [`Valor::Nulo`] se envía como un texto nulo. Devuelve la cantidad de filas afectadas.
+    pub async fn ejecutar(&self, consulta: &str, valores: &[Valor]) -> Result<u64, anyhow::Error> {
+        match self {
+            Conexion::MySql(pool) => ejecutar_en::<MySql, _>(consulta, valores, pool).await,
+            Conexion::Postgres(pool) => ejecutar_en::<Postgres, _>(consulta, valores, pool).await,
+            Conexion::Sqlite(pool) => ejecutar_en::<Sqlite, _>(consulta, valores, pool).await,
+        }
+    }
+}
+
+/// Implementación de [`Conexion::ejecutar`] para el motor `DB`.
+async fn ejecutar_en<'c, DB, E>(
+    consulta: &str,
+    valores: &[Valor],
+    ejecutor: E,
+) -> Result<u64, anyhow::Error>
+where
+    DB: Dialecto,
+    E: Executor<'c, Database = DB>,
+    for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+    for<'q> bool: Encode<'q, DB> + Type<DB>,
+    for<'q> i64: Encode<'q, DB> + Type<DB>,
+    for<'q> f64: Encode<'q, DB> + Type<DB>,
+    for<'q> String: Encode<'q, DB> + Type<DB>,
+    for<'q> Option<String>: Encode<'q, DB> + Type<DB>,
+    for<'q> Date: Encode<'q, DB> + Type<DB>,
+    for<'q> Time: Encode<'q, DB> + Type<DB>,
+{
+    let sql = if DB::PARAMETROS_NUMERADOS {
+        let mut numerada = String::with_capacity(consulta.len());
+        for (i, parte) in consulta.split('?').enumerate() {
+            if i > 0 {
+                let _ = write!(numerada, "${i}");
+            }
+            numerada.push_str(parte);
+        }
+        numerada
+    } else {
+        consulta.to_string()
+    };
+    let mut consulta = sqlx::query::<DB>(&sql);
+    for valor in valores {
+        consulta = match valor.clone() {
+            Valor::Nulo => consulta.bind(None::<String>),
+            Valor::Booleano(v) => consulta.bind(v),
+            Valor::Entero(v) => consulta.bind(v),
+            Valor::Real(v) => consulta.bind(v),
+            Valor::Decimal(v) => consulta.bind(DB::decimal(&v)),
+            Valor::Texto(v) => consulta.bind(v),
+            Valor::Fecha(v) => consulta.bind(v),
+            Valor::Hora(v) => consulta.bind(v),
+        };
+    }
+    Ok(DB::filas_afectadas(&consulta.execute(ejecutor).await?))
 }
@@ -0,0 +1,406 @@
+-- Traducción a PostgreSQL de migrations/20240606032226_cargar_tablas.sql.
+-- Postgres no tiene enteros sin signo ni enums en línea, por lo que se usa un dominio para los
+-- primeros y CHECK para los segundos.
+CREATE DOMAIN uint AS bigint CHECK (VALUE BETWEEN 0 AND 4294967295);
+CREATE TABLE Direcciones (
+	CodigoPostal uint,
+	Calle varchar(100),
+	Numero uint,
+	Localidad varchar(100) NOT NULL,
+	Provincia varchar(100) NOT NULL,
+	PRIMARY KEY (CodigoPostal, Calle, Numero)
+);
+
+CREATE TABLE Profesores (
+    DNI char(8),
+    CHECK (DNI ~ '^[0-9]{8}$'),
+	Nombre varchar(100) NOT NULL,
+	Apellido varchar(100) NOT NULL,
+	FechaNacimiento date NOT NULL,
+	Nacionalidad varchar(100) NOT NULL,
+	EstadoCivil varchar(12) CHECK (EstadoCivil IN ('Soltero/a', 'Casado/a', 'Divorciado/a', 'Viudo/a', 'Conviviente')) NOT NULL,
+	Sexo varchar(1) CHECK (Sexo IN ('M', 'F')) NOT NULL,
+	CUIT char(11),
+    CHECK (CUIT ~ '^[0-9]{11}$'),
+	CUIL char(11) NOT NULL,
+    CHECK (CUIL ~ '^[0-9]{11}$'),
+	CUITEmpleador char(11) NOT NULL,
+	PRIMARY KEY (DNI)
+);
+
+CREATE TABLE Instituciones (
+    Nombre varchar(255),
+	CodigoPostal uint,
+	Calle varchar(100),
+	Numero uint,
+    PRIMARY KEY (Nombre),
+    FOREIGN KEY (CodigoPostal, Calle, Numero) REFERENCES Direcciones(CodigoPostal, Calle, Numero)
+);
+
+CREATE TABLE Contactos (
+	DNIProfesor char(8),
+	Medio varchar(8) CHECK (Medio IN ('Celular', 'Telefono', 'Email')),
+	Direccion varchar(100),
+	Tipo varchar(11) CHECK (Tipo IN ('Personal', 'Empresarial', 'Otro')),
+	Numero varchar(30),
+	PRIMARY KEY (DNIProfesor, Tipo, Medio),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI) 
+    ON DELETE CASCADE ON UPDATE CASCADE,
+    CHECK (
+        (Medio IN ('Celular', 'Telefono') AND Numero IS NOT NULL) OR 
+        (Medio = 'Email' AND Direccion IS NOT NULL)
+    )
+);
+
+CREATE TABLE Idiomas (
+	Nombre varchar(50),
+	PRIMARY KEY (Nombre)
+);
+
+CREATE TABLE SeDaIdioma (
+    NombreIdioma varchar(50),
+    NombreInst varchar(255),
+    PRIMARY KEY(NombreIdioma, NombreInst),
+    FOREIGN KEY(NombreIdioma) REFERENCES Idiomas(Nombre),
+    FOREIGN KEY(NombreInst) REFERENCES Instituciones(Nombre)
+);
+
+CREATE TABLE ConoceIdioma (
+	DNIProfesor char(8),
+	NombreIdioma varchar(50),
+	Certificacion varchar(50) NOT NULL,
+	Nivel varchar(50) NOT NULL,
+	PRIMARY KEY (DNIProfesor, NombreIdioma),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI),
+	FOREIGN KEY (NombreIdioma) REFERENCES Idiomas(Nombre)
+);
+
+CREATE TABLE Titulos (
+	Nivel varchar(50),
+	Titulo varchar(100),
+	PRIMARY KEY (Nivel, Titulo)
+);
+
+CREATE TABLE SeDaTitulo (
+    Titulo varchar(100),
+    NombreInst varchar(255),
+    Nivel varchar(50),
+    PRIMARY KEY (Nivel, Titulo, NombreInst),
+    FOREIGN KEY (Nivel, Titulo) REFERENCES Titulos (Nivel, Titulo),
+    FOREIGN KEY (NombreInst) REFERENCES Instituciones (Nombre)
+);
+
+
+CREATE TABLE PoseeTitulo (
+	DNI char(8),
+	Nivel varchar(50),
+	Titulo varchar(100),
+	Desde date NOT NULL,
+	Hasta date NOT NULL,
+	PRIMARY KEY (DNI, Nivel, Titulo),
+	FOREIGN KEY (Nivel, Titulo) REFERENCES Titulos (Nivel, Titulo),
+	FOREIGN KEY (DNI) REFERENCES Profesores(DNI)
+);
+
+CREATE TABLE CursosConferencias (
+	NombreCurso varchar(100),
+	NombreInst varchar(255),
+	Descripcion varchar(255),
+	Tipo varchar(11) CHECK (Tipo IN ('Curso', 'Conferencia')) NOT NULL,
+	PRIMARY KEY (NombreCurso),
+    FOREIGN KEY (NombreInst) REFERENCES Instituciones(Nombre)
+);
+
+CREATE TABLE AtendioA (
+	NombreCurso varchar(100),
+	DNIProfesor char(8),
+	Desde date NOT NULL,
+	Hasta date,
+	PRIMARY KEY (NombreCurso, DNIProfesor),
+	FOREIGN KEY (NombreCurso) REFERENCES CursosConferencias (NombreCurso),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI)
+);
+
+
+CREATE TABLE ActividadesInvestigacion (
+	IDInvestigacion uint,
+	NombreInst varchar(255) NOT NULL,
+	Categoria varchar(50) NOT NULL,
+	AreaPPAL varchar(50) NOT NULL,
+	PRIMARY KEY (IDInvestigacion),
+    FOREIGN KEY (NombreInst) REFERENCES Instituciones(Nombre)
+);
+
+CREATE TABLE RealizaInves (
+	IDInvestigacion uint,
+	DNIProfesor char(8),
+	Desde date NOT NULL,
+	Hasta date,
+	Dedicacion uint NOT NULL,
+	PRIMARY KEY (IDInvestigacion, DNIProfesor),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI),
+	FOREIGN KEY (IDInvestigacion) REFERENCES ActividadesInvestigacion(IDInvestigacion)
+);
+
+CREATE TABLE ActividadesExtensionUniversitaria (
+	IDActividad uint,
+	NombreInst varchar(255) NOT NULL,
+	Cargo varchar(50) NOT NULL,
+	Categoria varchar(50) NOT NULL,
+	PRIMARY KEY (IDActividad),
+    FOREIGN KEY (NombreInst) REFERENCES Instituciones(Nombre)
+);
+
+CREATE TABLE RealizoAct(
+	IDActividad uint,
+	DNIProfesor char(8),
+	Acciones varchar(50) NOT NULL,
+	Dedicacion uint NOT NULL,
+	Hasta date NOT NULL,
+	Desde date NOT NULL,
+	PRIMARY KEY (DNIProfesor, IDActividad),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI),
+	FOREIGN KEY (IDActividad) REFERENCES ActividadesExtensionUniversitaria(IDActividad)
+);
+
+CREATE TABLE ObrasSociales (
+    NombreObra varchar(100),
+    IDObraSocial uint,
+    UNIQUE (NombreObra),
+    PRIMARY KEY (IDObraSocial)
+);
+
+CREATE TABLE DependenciasEmpresas (
+	DNIProfesor char(8),
+	Nombre varchar(100),
+	TipoActividad varchar(11) CHECK (TipoActividad IN ('Autónomo', 'Dependencia')) NOT NULL,
+	Observacion varchar(250) NOT NULL,
+	NaturalezaJuridica varchar(7) CHECK (NaturalezaJuridica IN ('Privado', 'Publico')),
+	CodigoPostal uint,
+	Calle varchar(100),
+	Numero uint,
+    IDObraSocial uint,
+    PRIMARY KEY (DNIProfesor, Nombre), 
+    FOREIGN KEY (IDObraSocial) REFERENCES ObrasSociales(IDObraSocial),
+	FOREIGN KEY (CodigoPostal, Calle, Numero) REFERENCES Direcciones(CodigoPostal, Calle, Numero),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI)
+    ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE TABLE DeclaracionesDeCargo (
+    DNIProfesor char(8),
+    NombreDep varchar(100),
+	IDDeclaracion uint,
+	CumpleHorario varchar(100) NOT NULL,
+	Reparticion varchar(100) NOT NULL,
+	Dependencia varchar(100) NOT NULL,
+	PRIMARY KEY (IDDeclaracion),
+    FOREIGN KEY (DNIProfesor, NombreDep) REFERENCES DependenciasEmpresas(DNIProfesor, Nombre)
+);
+
+CREATE TABLE AntecedentesProfesionales (
+	DNIProfesor char(8),
+    IDDeclaracion uint,
+	TipoActividad varchar(50),
+	Desde date not null,
+	Hasta date not null,
+	PRIMARY KEY (DNIProfesor, TipoActividad),
+    FOREIGN KEY(IDDeclaracion) REFERENCES DeclaracionesDeCargo(IDDeclaracion),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI)
+    ON DELETE CASCADE ON UPDATE	CASCADE
+);
+
+CREATE TABLE Publicaciones (
+	IDPublicacion uint,
+	Autores varchar(200) NOT NULL,
+	Anio smallint NOT NULL CHECK (Anio BETWEEN 1901 AND 2155),
+	Titulo varchar(50) NOT NULL,
+	PRIMARY KEY (IDPublicacion)
+);
+
+CREATE TABLE ReferenciaBibliografica (
+	IDFuente uint,
+	IDCitador uint,
+	PRIMARY KEY (IDFuente, IDCitador),
+	FOREIGN KEY (IDFuente) REFERENCES Publicaciones(IDPublicacion),
+	FOREIGN KEY (IDCitador) REFERENCES Publicaciones(IDPublicacion)
+);
+
+CREATE TABLE Publico(
+	IDPublicacion uint,
+	DNIProfesor char(8),
+	PRIMARY KEY (IDPublicacion, DNIProfesor),
+	FOREIGN KEY (IDPublicacion) REFERENCES Publicaciones(IDPublicacion),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI)
+);
+
+CREATE TABLE ReunionesCientificas (
+	Titulo varchar(50),
+	Fecha date,
+	PRIMARY KEY (Titulo, Fecha)
+);
+
+CREATE TABLE ParticipoEnReunion (
+	DNIProfesor char(8),
+	Titulo varchar(50),
+	Fecha date,
+	Participacion varchar(50),
+	PRIMARY KEY (DNIProfesor, Titulo, Fecha),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI),
+	FOREIGN KEY (Titulo, Fecha) REFERENCES ReunionesCientificas(Titulo, Fecha)
+);
+
+
+
+CREATE TABLE Familiares (
+    DNIProfesor char(8),
+	DNIFamiliar char(8),
+    CHECK (DNIFamiliar ~ '^[0-9]{8}$'),
+	Nombre varchar(50) NOT NULL,
+	Apellido varchar(50) NOT NULL,
+	Parentesco varchar(7) CHECK (Parentesco IN ('Cónyuge', 'Hijo', 'Padre', 'Pareja', 'Hermano')) NOT NULL,
+	FechaNacimiento date NOT NULL,
+	TipoDocumento varchar(50) NOT NULL,
+	Porcentaje Numeric NOT NULL,
+	NumeroDir uint NOT NULL,
+	CodigoPostal uint NOT NULL,
+	Calle varchar(100) NOT NULL,
+	Piso uint,
+	Departamento smallint,
+	PRIMARY KEY (DNIFamiliar, DNIProfesor),
+	FOREIGN KEY (CodigoPostal, Calle, NumeroDir) REFERENCES Direcciones (CodigoPostal, Calle, Numero),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI) 
+    ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE TABLE Beneficia (
+    DNIFamiliar char(8),
+    DNIProfesor char(8),
+    IDObraSocial uint,
+    PRIMARY KEY(IDObraSocial, DNIFamiliar, DNIProfesor),
+    FOREIGN KEY (IDObraSocial) REFERENCES ObrasSociales(IDObraSocial),
+    FOREIGN KEY (DNIFamiliar, DNIProfesor) REFERENCES Familiares(DNIFamiliar, DNIProfesor)
+);
+
+-- FIXME: DEFINIR CLAVE DE OBRA SOCIAL
+CREATE TABLE DocObraSocial (
+    IDDoc uint,
+	IDObraSocial uint,
+	-- En MySQL es decimal(8); Postgres no convierte implícitamente el texto del DNI a numeric.
+	DNIProfesor char(8),
+	-- FIXME: REVISAR SI ESTE ATRIBUTO HACE FALTA
+	TipoPersonal varchar(10) CHECK (TipoPersonal IN ('No Docente', 'Docente', 'Contratado', 'Becario')) NOT NULL,
+	TipoCaracter varchar(10) CHECK (TipoCaracter IN ('Titular', 'Suplente', 'Graduado', 'Estudiante', 'Interino')) NOT NULL,
+	PrestaServicios bool NOT NULL,
+	Dependencia varchar(100) NOT NULL,
+	PRIMARY KEY (IDObraSocial, IDDoc),
+    FOREIGN KEY (IDObraSocial) REFERENCES ObrasSociales(IDObraSocial)
+    ON UPDATE CASCADE ON DELETE CASCADE
+);
+
+CREATE TABLE Percepciones (
+	InstitucionCaja varchar(100),
+	Tipo varchar(50),
+	Regimen varchar(50) NOT NULL,
+	Causa varchar(50) NOT NULL,
+	PRIMARY KEY (Tipo, InstitucionCaja)
+);
+
+CREATE TABLE PercibeEn (
+	DNI char(8),
+	InstitucionCaja varchar(100),
+	Tipo varchar(50),
+	EstadoPercepcion varchar(11) CHECK (EstadoPercepcion IN ('Percibiendo', 'Suspendido')) NOT NULL,
+	Desde date NOT NULL,
+	PRIMARY KEY (DNI, Tipo, InstitucionCaja),
+	FOREIGN KEY (DNI) REFERENCES Profesores (DNI),
+	FOREIGN KEY (Tipo, InstitucionCaja) REFERENCES Percepciones (Tipo, InstitucionCaja)
+);
+
+CREATE TABLE DeclaracionesJuradas (
+	IDDeclaracion uint,
+	DNIProfesor char(8),
+	Fecha date not null,
+	Lugar varchar(100) not null,
+	PRIMARY KEY (DNIProfesor, IDDeclaracion),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI) ON
+	UPDATE CASCADE ON DELETE CASCADE
+);
+
+
+
+CREATE TABLE AntecedentesDocentes (
+	NombreInst varchar(255),
+	UnidadAcademica varchar(50),
+    IDDeclaracion uint,
+    DNIProfesor char(8),
+	Desde date NOT NULL,
+	Hasta date,
+	Dedicacion uint NOT NULL,
+	PRIMARY KEY (DNIProfesor, UnidadAcademica),
+    FOREIGN KEY (NombreInst) REFERENCES Instituciones(Nombre),
+    FOREIGN KEY (IDDeclaracion) REFERENCES DeclaracionesDeCargo(IDDeclaracion),
+	FOREIGN KEY (DNIProfesor) REFERENCES Profesores(DNI) 
+    ON DELETE CASCADE ON UPDATE	CASCADE
+);
+
+CREATE TABLE Horarios (
+	IDDeclaracion uint,
+	Dia varchar(9) CHECK (Dia IN ('Lunes', 'Martes', 'Miercoles', 'Jueves', 'Viernes')),
+	HoraInicio time,
+	HoraFin time,
+	NombreCatedra varchar(50),
+	PRIMARY KEY (IDDeclaracion, Dia, HoraInicio, HoraFin),
+	FOREIGN KEY (IDDeclaracion) REFERENCES DeclaracionesDeCargo(IDDeclaracion) 
+    ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE TABLE Empleadores (
+	CUIT char(11),
+    CHECK (CUIT ~ '^[0-9]{11}$'),
+	RazonSocial varchar(100),
+	CodigoPostal uint NOT NULL,
+	Calle varchar(100) NOT NULL,
+	Numero uint NOT NULL,
+	Piso uint,
+	Departamento smallint,
+	PRIMARY KEY (CUIT),
+	FOREIGN KEY (CodigoPostal, Calle, Numero) REFERENCES Direcciones (CodigoPostal, Calle, Numero)
+);
+
+CREATE TABLE ResideEn (
+	DNIProfesor char(8),
+	CodigoPostal uint,
+	Calle varchar(100),
+	Numero uint,
+	Piso uint,
+	Departamento smallint,
+	PRIMARY KEY (DNIProfesor, CodigoPostal, Calle, Numero),
+	FOREIGN KEY(CodigoPostal, Calle, Numero) REFERENCES Direcciones (CodigoPostal, Calle, Numero),
+	FOREIGN KEY(DNIProfesor) REFERENCES Profesores(DNI)
+);
+
+CREATE TABLE Seguros (
+	CodigoCompania uint,
+	CompaniaAseguradora varchar(100),
+	LugarEmision varchar(100),
+	FechaEmision date,
+	PRIMARY KEY(CodigoCompania)
+);
+
+
+CREATE TABLE AseguraA (
+	DNIProfesor char (8),
+	DNIFamiliar char (8),
+	CodigoCompania uint,
+	CapitalAsegurado Numeric,
+	FechaIngreso date,
+	PRIMARY KEY (DNIProfesor, DNIFamiliar, CodigoCompania),
+	FOREIGN KEY (CodigoCompania) REFERENCES Seguros (CodigoCompania),
+	FOREIGN KEY (DNIFamiliar, DNIProfesor) REFERENCES Familiares (DNIFamiliar, DNIProfesor)
+);
+
+ALTER TABLE
+	Profesores
+ADD
+	CONSTRAINT RefCuit2 FOREIGN KEY (CUITEmpleador) REFERENCES Empleadores (CUIT);
@@ -0,0 +1,269 @@
+// Autor: Acosta Quintana, Lautaro
+use serde::Serialize;
+use sqlx::{MySql, Pool};
+
+use crate::esquema::{Referencia, REFERENCIAS, TABLAS};
+
+/// Tipo de problema que busca cada control de la auditoría.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Categoria {
+    /// Filas que referencian a una fila inexistente de otra tabla.
+    Huerfanas,
+    /// Filas que no cumplen un CHECK o un enum de la migración.
+    Check,
+    /// Filas que no cumplen las reglas de negocio del TPI.
+    ReglaDeNegocio,
+}
+
+/// Resultado de un control de la auditoría.
+#[derive(Debug, Serialize)]
+pub struct Control {
+    pub categoria: Categoria,
+    pub tabla: String,
+    pub descripcion: String,
+    /// Cantidad de filas que no cumplen el control.
+    pub filas: i64,
+}
+
+impl Control {
+    pub fn es_valido(&self) -> bool {
+        self.filas == 0
+    }
+}
+
+/// Cantidad de filas de una tabla frente a la cantidad que se pidió generar.
+#[derive(Debug, Serialize)]
+pub struct Conteo {
+    pub tabla: String,
+    pub filas: i64,
+    /// `None` si la cantidad de filas de la tabla no depende directamente de `--cantidad`.
+    pub esperadas: Option<i64>,
+}
+
+impl Conteo {
+    pub fn es_valido(&self) -> bool {
+        !matches!(self.esperadas, Some(e) if self.filas < e)
+    }
+}
+
+/// Resultado completo de auditar una base de datos.
+#[derive(Debug, Serialize)]
+pub struct Auditoria {
+    pub controles: Vec<Control>,
+    pub conteos: Vec<Conteo>,
+}
+
+impl Auditoria {
+    /// Cantidad de controles y conteos que fallaron.
+    pub fn fallas(&self) -> usize {
+        self.controles.iter().filter(|c| !c.es_valido()).count()
+            + self.conteos.iter().filter(|c| !c.es_valido()).count()
+    }
+}
+
+/// Controles sobre los CHECK y enums de la migración. Los valores inválidos sólo pueden aparecer si
+/// los datos se cargaron con los controles desactivados o con el modo estricto apagado, en cuyo
+/// caso MySQL guarda los enums inválidos como ''.
+const CHECKS: &[(&str, &str, &str)] = &[
+    ("Profesores", "DNI de 8 dígitos", "NOT (DNI REGEXP '^[0-9]{8}$')"),
+    ("Profesores", "CUIT de 11 dígitos", "NOT (CUIT REGEXP '^[0-9]{11}$')"),
+    ("Profesores", "CUIL de 11 dígitos", "NOT (CUIL REGEXP '^[0-9]{11}$')"),
+    ("Profesores", "EstadoCivil válido", "EstadoCivil = ''"),
+    ("Profesores", "Sexo válido", "Sexo = ''"),
+    ("Familiares", "DNIFamiliar de 8 dígitos", "NOT (DNIFamiliar REGEXP '^[0-9]{8}$')"),
+    ("Familiares", "Parentesco válido", "Parentesco = ''"),
+    ("Empleadores", "CUIT de 11 dígitos", "NOT (CUIT REGEXP '^[0-9]{11}$')"),
+    (
+        "Contactos",
+        "Numero para Celular y Telefono, Direccion para Email",
+        "NOT ((Medio IN ('Celular', 'Telefono') AND Numero IS NOT NULL) OR (Medio = 'Email' AND Direccion IS NOT NULL))",
+    ),
+    ("Contactos", "Medio y Tipo válidos", "Medio = '' OR Tipo = ''"),
+    ("DependenciasEmpresas", "TipoActividad válido", "TipoActividad = ''"),
+    ("DependenciasEmpresas", "NaturalezaJuridica válida", "NaturalezaJuridica = ''"),
+    ("CursosConferencias", "Tipo válido", "Tipo = ''"),
+    ("DocObraSocial", "TipoPersonal y TipoCaracter válidos", "TipoPersonal = '' OR TipoCaracter = ''"),
+    ("PercibeEn", "EstadoPercepcion válido", "EstadoPercepcion = ''"),
+    ("Horarios", "Dia válido", "Dia = ''"),
+];
+
+/// Controles sobre las reglas de negocio, cada uno con una consulta que cuenta las filas que no
+/// las cumplen.
+const REGLAS: &[(&str, &str, &str)] = &[
+    (
+        "Familiares",
+        "Un profesor tiene a lo sumo un Cónyuge",
+        "SELECT COUNT(*) FROM (
+            SELECT DNIProfesor FROM Familiares WHERE Parentesco = 'Cónyuge'
+            GROUP BY DNIProfesor HAVING COUNT(*) > 1
+        ) AS t",
+    ),
+    (
+        "Horarios",
+        "Los horarios de una declaración no se superponen",
+        "SELECT COUNT(*) FROM Horarios a JOIN Horarios b
+            ON a.IDDeclaracion = b.IDDeclaracion AND a.Dia = b.Dia
+            AND (a.HoraInicio, a.HoraFin) < (b.HoraInicio, b.HoraFin)
+            AND a.HoraInicio < b.HoraFin AND b.HoraInicio < a.HoraFin",
+    ),
+    (
+        "Horarios",
+        "HoraFin es posterior a HoraInicio",
+        "SELECT COUNT(*) FROM Horarios WHERE HoraFin <= HoraInicio",
+    ),
+    (
+        "PoseeTitulo",
+        "Desde no es posterior a Hasta",
+        "SELECT COUNT(*) FROM PoseeTitulo WHERE Desde > Hasta",
+    ),
+    (
+        "AntecedentesDocentes",
+        "Desde no es posterior a Hasta",
+        "SELECT COUNT(*) FROM AntecedentesDocentes WHERE Desde > Hasta",
+    ),
+    (
+        "AntecedentesProfesionales",
+        "Desde no es posterior a Hasta",
+        "SELECT COUNT(*) FROM AntecedentesProfesionales WHERE Desde > Hasta",
+    ),
+    (
+        "RealizaInves",
+        "Desde no es posterior a Hasta",
+        "SELECT COUNT(*) FROM RealizaInves WHERE Desde > Hasta",
+    ),
+    (
+        "RealizoAct",
+        "Desde no es posterior a Hasta",
+        "SELECT COUNT(*) FROM RealizoAct WHERE Desde > Hasta",
+    ),
+    (
+        "AtendioA",
+        "Desde no es posterior a Hasta",
+        "SELECT COUNT(*) FROM AtendioA WHERE Desde > Hasta",
+    ),
+    (
+        "ReferenciaBibliografica",
+        "Una publicación no se cita a sí misma",
+        "SELECT COUNT(*) FROM ReferenciaBibliografica WHERE IDFuente = IDCitador",
+    ),
+    (
+        "ReferenciaBibliografica",
+        "Una publicación sólo cita publicaciones anteriores",
+        "SELECT COUNT(*) FROM ReferenciaBibliografica r
+            JOIN Publicaciones f ON f.IDPublicacion = r.IDFuente
+            JOIN Publicaciones c ON c.IDPublicacion = r.IDCitador
+            WHERE f.Anio >= c.Anio",
+    ),
+];
+
+/// Tablas cuya cantidad de filas es igual a la indicada con `--cantidad`.
+const TABLAS_POR_CANTIDAD: [&str; 16] = [
+    "Direcciones",
+    "Titulos",
+    "Publicaciones",
+    "ReunionesCientificas",
+    "Percepciones",
+    "Seguros",
+    "Empleadores",
+    "CursosConferencias",
+    "ActividadesExtensionUniversitaria",
+    "ActividadesInvestigacion",
+    "Profesores",
+    "DeclaracionesDeCargo",
+    "AntecedentesProfesionales",
+    "AntecedentesDocentes",
+    "Horarios",
+    "ObrasSociales",
+];
+
+fn consulta_huerfanas(r: &Referencia) -> String {
+    let no_nulas = r
+        .columnas
+        .iter()
+        .map(|c| format!("h.{c} IS NOT NULL"))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let union = r
+        .columnas
+        .iter()
+        .zip(r.columnas_referidas)
+        .map(|(c, p)| format!("p.{p} = h.{c}"))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    format!(
+        "SELECT COUNT(*) FROM {} h WHERE {no_nulas} AND NOT EXISTS (SELECT 1 FROM {} p WHERE {union})",
+        r.tabla, r.referida
+    )
+}
+
+async fn contar(pool: &Pool<MySql>, consulta: &str) -> anyhow::Result<i64> {
+    Ok(sqlx::query_scalar(consulta).fetch_one(pool).await?)
+}
+
+/// Revisa una base de datos ya cargada en busca de filas huérfanas, valores que no cumplen los
+/// CHECK de la migración y violaciones a las reglas de negocio, y compara la cantidad de filas de
+/// cada tabla con `cantidad`. Las instituciones se limitan a las `universidades` disponibles y los
+/// títulos a los `titulos` del dataset.
+pub async fn auditar(
+    pool: &Pool<MySql>,
+    cantidad: usize,
+    universidades: usize,
+    titulos: usize,
+) -> anyhow::Result<Auditoria> {
+    let mut controles = Vec::new();
+
+    for r in REFERENCIAS {
+        let descripcion = format!(
+            "({}) referencia a {}({}){}",
+            r.columnas.join(", "),
+            r.referida,
+            r.columnas_referidas.join(", "),
+            if r.declarada { "" } else { " sin FOREIGN KEY" }
+        );
+        controles.push(Control {
+            categoria: Categoria::Huerfanas,
+            tabla: r.tabla.to_string(),
+            descripcion,
+            filas: contar(pool, &consulta_huerfanas(r)).await?,
+        });
+    }
+
+    for (tabla, descripcion, condicion) in CHECKS {
+        let consulta = format!("SELECT COUNT(*) FROM {tabla} WHERE {condicion}");
+        controles.push(Control {
+            categoria: Categoria::Check,
+            tabla: tabla.to_string(),
+            descripcion: descripcion.to_string(),
+            filas: contar(pool, &consulta).await?,
+        });
+    }
+
+    for (tabla, descripcion, consulta) in REGLAS {
+        controles.push(Control {
+            categoria: Categoria::ReglaDeNegocio,
+            tabla: tabla.to_string(),
+            descripcion: descripcion.to_string(),
+            filas: contar(pool, consulta).await?,
+        });
+    }
+
+    let mut conteos = Vec::new();
+    for tabla in TABLAS {
+        let filas = contar(pool, &format!("SELECT COUNT(*) FROM {tabla}")).await?;
+        let esperadas = match tabla {
+            "Instituciones" => Some(cantidad.min(universidades)),
+            "Titulos" => Some(cantidad.min(titulos)),
+            // Además de las generadas se carga la obra social D.A.S.U.Te.N.
+            "ObrasSociales" => Some(cantidad + 1),
+            t if TABLAS_POR_CANTIDAD.contains(&t) => Some(cantidad),
+            _ => None,
+        };
+        conteos.push(Conteo {
+            tabla: tabla.to_string(),
+            filas,
+            esperadas: esperadas.map(|e| e as i64),
+        });
+    }
+
+    Ok(Auditoria { controles, conteos })
+}
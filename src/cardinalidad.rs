@@ -0,0 +1,204 @@
+// Autor: Acosta Quintana, Lautaro
+use std::fmt;
+
+use dbdata::Conexion;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::distribucion::{buscar_relacion, Relacion};
+
+/// Cantidad mínima y máxima de hijos que debe tener cada padre de una relación.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cardinalidad {
+    pub relacion: Relacion,
+    pub min: usize,
+    /// `None` si la cantidad de hijos no tiene límite.
+    pub max: Option<usize>,
+}
+
+impl Cardinalidad {
+    /// Ajusta una asignación de hijos a padres (ver [`crate::distribucion::Distribucion::asignar`])
+    /// para que cada padre tenga entre `min` y `max` hijos, descartando los sobrantes y agregando
+    /// los faltantes.
+    pub fn ajustar<R: Rng + ?Sized>(
+        &self,
+        mut asignacion: Vec<usize>,
+        padres: usize,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let mut hijos = vec![0usize; padres];
+        if let Some(max) = self.max {
+            asignacion.retain(|&p| {
+                hijos[p] += 1;
+                hijos[p] <= max
+            });
+        } else {
+            asignacion.iter().for_each(|&p| hijos[p] += 1);
+        }
+
+        let previos = asignacion.len();
+        for (p, &cantidad) in hijos.iter().enumerate() {
+            asignacion.extend((cantidad..self.min).map(|_| p));
+        }
+        // Se mezclan para no agrupar los hijos agregados al final.
+        if asignacion.len() > previos {
+            asignacion.shuffle(rng);
+        }
+        asignacion
+    }
+}
+
+impl fmt::Display for Cardinalidad {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {}: {}..",
+            self.relacion.padre, self.relacion.hijo, self.min
+        )?;
+        match self.max {
+            Some(max) => write!(f, "{max}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Interpreta una cardinalidad de la forma `<Padre>-><Hijo>:<min>..<max>`, donde `<max>` se puede
+/// omitir.
+pub fn parsear_cardinalidad(s: &str) -> Result<Cardinalidad, String> {
+    let (relacion, rango) = s
+        .split_once(':')
+        .ok_or_else(|| format!("Se esperaba '<Padre>-><Hijo>:<min>..<max>' en '{s}'."))?;
+    let (padre, hijo) = relacion
+        .split_once("->")
+        .ok_or_else(|| format!("Se esperaba '<Padre>-><Hijo>' en '{relacion}'."))?;
+    let relacion = buscar_relacion(hijo.trim())?;
+    if relacion.padre != padre.trim() {
+        return Err(format!(
+            "La tabla padre de '{}' es '{}', no '{}'.",
+            relacion.hijo,
+            relacion.padre,
+            padre.trim()
+        ));
+    }
+
+    let (min, max) = rango
+        .split_once("..")
+        .ok_or_else(|| format!("Se esperaba '<min>..<max>' en '{rango}'."))?;
+    let entero = |v: &str| {
+        v.trim()
+            .parse::<usize>()
+            .map_err(|_| format!("'{v}' no es un entero válido en '{s}'."))
+    };
+    let min = entero(min)?;
+    let max = match max.trim() {
+        "" => None,
+        max => Some(entero(max)?),
+    };
+    if max.is_some_and(|max| max < min) {
+        return Err(format!("El mínimo de '{s}' es mayor al máximo."));
+    }
+
+    Ok(Cardinalidad { relacion, min, max })
+}
+
+/// Cardinalidades que debe respetar el generador.
+#[derive(Debug, Clone)]
+pub struct Cardinalidades(Vec<Cardinalidad>);
+
+impl Default for Cardinalidades {
+    /// Las reglas de negocio del TPI: cada profesor tiene al menos un contacto, una declaración
+    /// jurada y un domicilio.
+    fn default() -> Self {
+        Self(
+            [
+                "Profesores->Contactos:1..",
+                "Profesores->DeclaracionesJuradas:1..",
+                "Profesores->ResideEn:1..",
+            ]
+            .into_iter()
+            .map(|c| parsear_cardinalidad(c).expect("Las cardinalidades por defecto son válidas"))
+            .collect(),
+        )
+    }
+}
+
+impl Cardinalidades {
+    /// Agrega una cardinalidad, reemplazando la que hubiera para la misma relación.
+    pub fn configurar(&mut self, cardinalidad: Cardinalidad) {
+        self.0.retain(|c| c.relacion != cardinalidad.relacion);
+        self.0.push(cardinalidad);
+    }
+
+    pub fn de(&self, hijo: &str) -> Option<&Cardinalidad> {
+        self.0.iter().find(|c| c.relacion.hijo == hijo)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Cardinalidad> {
+        self.0.iter()
+    }
+
+    /// Aplica la cardinalidad configurada para `hijo`, si la hay, a la asignación de hijos a
+    /// padres.
+    pub fn aplicar<R: Rng + ?Sized>(
+        &self,
+        hijo: &str,
+        asignacion: Vec<usize>,
+        padres: usize,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        match self.de(hijo) {
+            Some(c) => c.ajustar(asignacion, padres, rng),
+            None => asignacion,
+        }
+    }
+}
+
+/// Resultado de comprobar una cardinalidad contra los datos cargados.
+#[derive(Debug)]
+pub struct InformeCardinalidad {
+    pub cardinalidad: Cardinalidad,
+    /// Cantidad de padres con menos hijos que el mínimo.
+    pub por_debajo: i64,
+    /// Cantidad de padres con más hijos que el máximo.
+    pub por_encima: i64,
+}
+
+impl InformeCardinalidad {
+    pub fn se_cumple(&self) -> bool {
+        self.por_debajo == 0 && self.por_encima == 0
+    }
+}
+
+/// Cuenta, para cada cardinalidad, cuántos padres cargados en la base de datos no la respetan.
+pub async fn comprobar_cardinalidades(
+    cardinalidades: &Cardinalidades,
+    conexion: &Conexion,
+) -> anyhow::Result<Vec<InformeCardinalidad>> {
+    let mut informes = Vec::new();
+    for c in cardinalidades.iter() {
+        let Relacion {
+            padre,
+            clave_padre,
+            hijo,
+            clave_hijo,
+        } = c.relacion;
+        // Se cuentan los hijos de cada padre y se comparan acá para que la consulta funcione en
+        // todos los motores.
+        let consulta = format!(
+            "SELECT COUNT(h.{clave_hijo}) FROM {padre} p
+            LEFT JOIN {hijo} h ON h.{clave_hijo} = p.{clave_padre}
+            GROUP BY p.{clave_padre}"
+        );
+        let hijos: Vec<i64> = match conexion {
+            Conexion::MySql(pool) => sqlx::query_scalar(&consulta).fetch_all(pool).await?,
+            Conexion::Postgres(pool) => sqlx::query_scalar(&consulta).fetch_all(pool).await?,
+            Conexion::Sqlite(pool) => sqlx::query_scalar(&consulta).fetch_all(pool).await?,
+        };
+        let max = c.max.map_or(i64::MAX, |m| m as i64);
+        informes.push(InformeCardinalidad {
+            cardinalidad: *c,
+            por_debajo: hijos.iter().filter(|&&n| n < c.min as i64).count() as i64,
+            por_encima: hijos.iter().filter(|&&n| n > max).count() as i64,
+        });
+    }
+    Ok(informes)
+}
@@ -0,0 +1,1015 @@
+// Autor: Acosta Quintana, Lautaro
+use std::collections::{HashMap, VecDeque};
+
+use rand::{
+    rngs::StdRng,
+    seq::{IteratorRandom, SliceRandom},
+    Rng, SeedableRng,
+};
+
+use crate::{
+    cardinalidad::Cardinalidades,
+    cargar_tabla,
+    citas::Citas,
+    datasets::{Datasets, TipoPercepcion, TITULOS},
+    db_tablas::*,
+    destino::Destino,
+    distribucion::{Distribucion, Distribuciones},
+    notificar_carga,
+    Notificacion::INFO,
+};
+
+/* Orden de carga hasta ahora:
+- Primero aquellas tablas que no tienen FKs.
+    01. Direcciones
+    02. Titulos
+    03. Reuniones
+    04. Percepciones
+    05. Seguros
+    06. ObrasSociales
+    07. Idiomas
+
+- Segundo, aquellas tablas que contienen FKs.
+
+    08. Empleadores
+    09. Instituciones
+    10. CursosConferencias
+    11. ActividadesExtensionUniversitaria
+    12. ActividadesInvestigacion
+    13. Profesores
+    14. Publicaciones (sin FKs, pero sus autores son profesores)
+    15. Contactos
+    16. DependenciasEmpresas
+    17. Familiares
+    18. DocObraSocial
+    19. DeclaracionesJuradas
+    20. DeclaracionesDeCargo
+    21. AntecedentesProfesionales
+    22. AntecedentesDocentes
+    23. Horarios
+    24. AtendioA
+    25. SeDaIdiomas
+    26. ConoceIdiomas
+    27. Beneficia
+    28. PoseeTitulo
+    29. SeDaTitulo
+    30. RealizaInvestigacion
+    31. RealizoActividad
+    32. ReferenciasBibliograficas
+    33. Publico
+    34. ParticipoEnReunion
+    35. PercibeEn
+    36. ResideEn
+    37. AseguraA
+*/
+
+/// Tablas cuya cantidad de filas se puede indicar con [`OpcionesCarga::cantidades`]. La cantidad
+/// de filas del resto de las tablas depende de las de otras tablas, de la distribución o de los
+/// datasets.
+pub const TABLAS_CON_CANTIDAD: [&str; 24] = [
+    "Direcciones",
+    "Titulos",
+    "ReunionesCientificas",
+    "Percepciones",
+    "Seguros",
+    "ObrasSociales",
+    "Empleadores",
+    "Instituciones",
+    "CursosConferencias",
+    "ActividadesExtensionUniversitaria",
+    "ActividadesInvestigacion",
+    "Profesores",
+    "Publicaciones",
+    "DependenciasEmpresas",
+    "Familiares",
+    "DocObraSocial",
+    "DeclaracionesJuradas",
+    "DeclaracionesDeCargo",
+    "AntecedentesProfesionales",
+    "AntecedentesDocentes",
+    "Horarios",
+    "Beneficia",
+    "RealizaInves",
+    "RealizoAct",
+];
+
+/// Parámetros con los que se generan los datos.
+#[derive(Debug, Clone)]
+pub struct OpcionesCarga {
+    /// Cantidad de registros a generar en cada tabla.
+    pub muestras: usize,
+    /// Cantidad de registros de algunas de las [`TABLAS_CON_CANTIDAD`] en lugar de `muestras`.
+    pub cantidades: HashMap<String, usize>,
+    pub distribuciones: Distribuciones,
+    pub cardinalidades: Cardinalidades,
+    /// Semilla con la que se generan los datos; con la misma semilla, las mismas opciones y los
+    /// mismos datasets se generan las mismas filas. Sin semilla se elige una al azar.
+    pub semilla: Option<u64>,
+    /// Proporción, entre 0 y 1, de obras sociales, aseguradoras y cajas previsionales inventadas
+    /// en lugar de tomadas de los datasets. Cuando se agotan las de los datasets las demás también
+    /// se inventan.
+    pub ficticias: f64,
+    /// Cantidad de referencias bibliográficas de cada publicación y cómo se eligen sus fuentes.
+    pub citas: Citas,
+}
+
+impl Default for OpcionesCarga {
+    fn default() -> Self {
+        Self {
+            muestras: 1000,
+            cantidades: HashMap::new(),
+            distribuciones: Distribuciones::default(),
+            cardinalidades: Cardinalidades::default(),
+            semilla: None,
+            ficticias: 0.0,
+            citas: Citas::default(),
+        }
+    }
+}
+
+impl OpcionesCarga {
+    /// Cantidad de registros a generar en `tabla`.
+    pub fn cantidad(&self, tabla: &str) -> usize {
+        self.cantidades.get(tabla).copied().unwrap_or(self.muestras)
+    }
+}
+
+/// Filas de cada tabla que el destino aceptó, que son las únicas que se usan como referencia en las
+/// tablas que dependen de ellas. La carga masiva acepta todas las filas al acumularlas.
+#[derive(Debug, Clone, Default)]
+pub struct Generados {
+    pub direcciones: Vec<Direcciones>,
+    pub titulos: Vec<Titulos>,
+    pub publicaciones: Vec<Publicaciones>,
+    pub reuniones_cientificas: Vec<ReunionesCientificas>,
+    pub percepciones: Vec<Percepciones>,
+    pub seguros: Vec<Seguros>,
+    pub obras_sociales: Vec<ObrasSociales>,
+    /// Vacío si la tabla Idiomas ya tenía filas.
+    pub idiomas: Vec<Idiomas>,
+    pub empleadores: Vec<Empleadores>,
+    pub instituciones: Vec<Instituciones>,
+    pub cursos_conferencias: Vec<CursosConferencias>,
+    pub actividades_extension_universitaria: Vec<ActividadesExtensionUniversitaria>,
+    pub actividades_investigacion: Vec<ActividadesInvestigacion>,
+    pub profesores: Vec<Profesores>,
+    pub contactos: Vec<Contactos>,
+    pub dependencias_empresas: Vec<DependenciasEmpresas>,
+    pub familiares: Vec<Familiares>,
+    pub doc_obra_social: Vec<DocObraSocial>,
+    pub declaraciones_juradas: Vec<DeclaracionesJuradas>,
+    pub declaraciones_de_cargo: Vec<DeclaracionesDeCargo>,
+    pub antecedentes_profesionales: Vec<AntecedentesProfesionales>,
+    pub antecedentes_docentes: Vec<AntecedentesDocentes>,
+    pub horarios: Vec<Horarios>,
+    pub atendio_a: Vec<AtendioA>,
+    pub conoce_idioma: Vec<ConoceIdioma>,
+    pub se_da_idioma: Vec<SeDaIdioma>,
+    pub beneficia: Vec<Beneficia>,
+    pub posee_titulo: Vec<PoseeTitulo>,
+    pub se_da_titulo: Vec<SeDaTitulo>,
+    pub realiza_inves: Vec<RealizaInves>,
+    pub realizo_act: Vec<RealizoAct>,
+    pub referencia_bibliografica: Vec<ReferenciaBibliografica>,
+    pub publico: Vec<Publico>,
+    pub participo_en_reunion: Vec<ParticipoEnReunion>,
+    pub percibe_en: Vec<PercibeEn>,
+    pub reside_en: Vec<ResideEn>,
+    pub asegura_a: Vec<AseguraA>,
+}
+
+/// Elige `cantidad` entidades de `reales` sin repetirlas, en orden aleatorio, o `None` en lugar de
+/// las que se deben inventar: cada una con probabilidad `ficticias` y todas las que faltan cuando se
+/// agotan las reales.
+fn elegir_reales<'a, T, R: Rng + ?Sized>(
+    reales: &'a [T],
+    cantidad: usize,
+    ficticias: f64,
+    rng: &mut R,
+) -> Vec<Option<&'a T>> {
+    let mut disponibles: Vec<&T> = reales.iter().collect();
+    disponibles.shuffle(rng);
+    let mut disponibles = disponibles.into_iter();
+    (0..cantidad)
+        .map(|_| {
+            if rng.gen_bool(ficticias) {
+                None
+            } else {
+                disponibles.next()
+            }
+        })
+        .collect()
+}
+
+/// Cantidad al azar en `cantidad / 2..cantidad`, o 0 si `cantidad` es 0 o 1.
+fn cantidad_parcial<R: Rng + ?Sized>(cantidad: usize, rng: &mut R) -> usize {
+    rng.gen_range(cantidad / 2..cantidad.max(1))
+}
+
+/// Máximo de profesores autores de una publicación.
+pub const MAX_AUTORES: usize = 3;
+
+/// Elige los profesores autores de cada una de `publicaciones` publicaciones, como índices entre
+/// `profesores`. Cada publicación tiene entre 1 y [`MAX_AUTORES`] profesores distintos, salvo que
+/// la distribución o la cardinalidad de `Publico` generen menos autorías que publicaciones. Las
+/// autorías que no entran en ninguna publicación se descartan.
+fn repartir_autorias<R: Rng + ?Sized>(
+    profesores: usize,
+    publicaciones: usize,
+    opciones: &OpcionesCarga,
+    rng: &mut R,
+) -> Vec<Vec<usize>> {
+    let lugares: Vec<usize> = (0..publicaciones)
+        .map(|_| rng.gen_range(1..=MAX_AUTORES))
+        .collect();
+    let autorias = opciones.distribuciones.asignar(
+        "Publico",
+        Distribucion::Uniforme,
+        profesores,
+        lugares.iter().sum(),
+        rng,
+    );
+    let mut autorias = opciones
+        .cardinalidades
+        .aplicar("Publico", autorias, profesores, rng);
+    autorias.shuffle(rng);
+
+    // Las publicaciones se llenan por turnos, para que todas tengan un autor antes de que alguna
+    // tenga dos.
+    let mut autores = vec![Vec::new(); publicaciones];
+    let mut pendientes: VecDeque<usize> = (0..publicaciones).collect();
+    for p in autorias {
+        for _ in 0..pendientes.len() {
+            let Some(i) = pendientes.pop_front() else {
+                break;
+            };
+            if autores[i].contains(&p) {
+                pendientes.push_back(i);
+                continue;
+            }
+            autores[i].push(p);
+            if autores[i].len() < lugares[i] {
+                pendientes.push_back(i);
+            }
+            break;
+        }
+    }
+    autores
+}
+
+/// Genera los datos de las 37 tablas y los envía a `destino`, que si es una base de datos ya debe
+/// tener las tablas creadas. Devuelve las filas generadas.
+///
+/// Con cantidades chicas algunas tablas pueden quedar vacías; en ese caso no se generan las filas
+/// que las referencian.
+pub async fn cargar(
+    destino: &dyn Destino,
+    datasets: Datasets,
+    opciones: &OpcionesCarga,
+) -> anyhow::Result<Generados> {
+    let muestras = opciones.muestras;
+    let cantidad = |tabla: &str| opciones.cantidad(tabla);
+    let distribuciones = &opciones.distribuciones;
+    let cardinalidades = &opciones.cardinalidades;
+    let Datasets {
+        universidades: mut nombre_universidades,
+        provincias,
+        idiomas,
+        titulos: mut datos_titulos,
+        materias,
+        categorias_investigacion,
+        certificaciones,
+        percepciones: tipos_percepcion,
+        obras_sociales: datos_obras_sociales,
+        aseguradoras,
+        cajas_previsionales,
+    } = datasets;
+    anyhow::ensure!(
+        (0.0..=1.0).contains(&opciones.ficticias),
+        "La proporción de entidades ficticias debe estar entre 0 y 1, no {}.",
+        opciones.ficticias
+    );
+    anyhow::ensure!(
+        opciones.citas.atraccion >= 0.0 && opciones.citas.atraccion.is_finite(),
+        "La atracción de las citas debe ser un número no negativo, no {}.",
+        opciones.citas.atraccion
+    );
+    // Cada profesor necesita un título terciario, que siempre es el primero que se carga.
+    anyhow::ensure!(
+        cantidad("Profesores") == 0
+            || cantidad("Titulos") > 0 && datos_titulos.iter().any(|t| t.nivel == "Terciario"),
+        "Cada profesor necesita un título terciario: se debe cargar al menos un título y el \
+        dataset {} debe tener títulos de nivel Terciario.",
+        TITULOS.archivo
+    );
+    anyhow::ensure!(
+        opciones.citas.min <= opciones.citas.max,
+        "El mínimo de referencias por publicación es mayor al máximo: {}.",
+        opciones.citas
+    );
+    let idiomas: Vec<Idiomas> = idiomas.iter().map(|x| Idiomas::new(x)).collect();
+    let mut rng = match opciones.semilla {
+        Some(semilla) => StdRng::seed_from_u64(semilla),
+        None => StdRng::from_entropy(),
+    };
+    // Las filas que se generan por sí solas usan su propio generador, que se inicializa a partir
+    // de este para que toda la carga dependa de una sola semilla.
+    sembrar(rng.gen());
+
+    // Primero aquellas tablas que no tienen FK.
+    let mut direcciones = Vec::with_capacity(cantidad("Direcciones"));
+    for _ in 1..=cantidad("Direcciones") {
+        let Some(provincia) = provincias.choose(&mut rng) else {
+            break;
+        };
+        let Some(localidad) = provincia.localidades.choose(&mut rng) else {
+            break;
+        };
+        let Some(calle) = localidad.calles.choose(&mut rng) else {
+            break;
+        };
+        let fila = Direcciones::new(&provincia.nombre, &localidad.nombre, calle);
+        if destino.enviar(&fila).await? {
+            direcciones.push(fila);
+        }
+    }
+
+    notificar_carga(INFO, "Direcciones");
+
+    let mut titulos = Vec::with_capacity(cantidad("Titulos"));
+    datos_titulos.shuffle(&mut rng);
+    // El primer título terciario y el primero de otro nivel van adelante, para que aun con pocos
+    // títulos cada profesor tenga un terciario y haya títulos de otros niveles.
+    for terciario in [false, true] {
+        if let Some(i) = datos_titulos
+            .iter()
+            .position(|t| (t.nivel == "Terciario") == terciario)
+        {
+            datos_titulos[..=i].rotate_right(1);
+        }
+    }
+    for titulo in datos_titulos.iter().take(cantidad("Titulos")) {
+        let fila = Titulos::new(titulo);
+        if destino.enviar(&fila).await? {
+            titulos.push(fila);
+        }
+    }
+    notificar_carga(INFO, "Titulos");
+
+    let reuniones =
+        cargar_tabla::<ReunionesCientificas>(cantidad("ReunionesCientificas"), destino).await?;
+    // Cada tipo de percepción en cada caja se carga una sola vez.
+    let combinaciones: Vec<(&TipoPercepcion, &String)> = tipos_percepcion
+        .iter()
+        .flat_map(|t| cajas_previsionales.iter().map(move |c| (t, c)))
+        .collect();
+    let mut percepciones = Vec::with_capacity(cantidad("Percepciones"));
+    for real in elegir_reales(
+        &combinaciones,
+        cantidad("Percepciones"),
+        opciones.ficticias,
+        &mut rng,
+    ) {
+        let fila = match real {
+            Some((tipo, caja)) => Percepciones::new(tipo, Some(caja)),
+            None => match tipos_percepcion.choose(&mut rng) {
+                Some(tipo) => Percepciones::new(tipo, None),
+                None => break,
+            },
+        };
+        if destino.enviar(&fila).await? {
+            percepciones.push(fila);
+        }
+    }
+    notificar_carga(INFO, "Percepciones");
+
+    let mut seguros = Vec::with_capacity(cantidad("Seguros"));
+    for real in elegir_reales(
+        &aseguradoras,
+        cantidad("Seguros"),
+        opciones.ficticias,
+        &mut rng,
+    ) {
+        let fila = match real {
+            Some(compania) => Seguros::new(compania),
+            None => generar::<Seguros>(),
+        };
+        if destino.enviar(&fila).await? {
+            seguros.push(fila);
+        }
+    }
+    notificar_carga(INFO, "Seguros");
+
+    let mut obras_sociales = Vec::with_capacity(cantidad("ObrasSociales") + 1);
+    for real in elegir_reales(
+        &datos_obras_sociales,
+        cantidad("ObrasSociales"),
+        opciones.ficticias,
+        &mut rng,
+    ) {
+        let fila = match real {
+            Some(obra) => ObrasSociales::new(&obra.nombre, obra.rnos),
+            None => generar::<ObrasSociales>(),
+        };
+        if destino.enviar(&fila).await? {
+            obras_sociales.push(fila);
+        }
+    }
+    let dasuten = ObrasSociales::new("D.A.S.U.Te.N", rng.gen());
+    if destino.enviar(&dasuten).await? {
+        obras_sociales.push(dasuten);
+    }
+    notificar_carga(INFO, "ObrasSociales");
+
+    let mut idiomas_cargados = Vec::new();
+    if !destino.tiene_filas("Idiomas").await? {
+        for i in &idiomas {
+            if destino.enviar(i).await? {
+                idiomas_cargados.push(i.clone());
+            }
+        }
+    }
+
+    notificar_carga(INFO, "Idiomas");
+    //cargar_idiomas(&idiomas, conexion).await?;
+
+    let mut empleadores = Vec::with_capacity(cantidad("Empleadores"));
+    for _ in 1..=cantidad("Empleadores") {
+        let Some(direccion) = direcciones.choose(&mut rng) else {
+            break;
+        };
+        let fila = Empleadores::new(direccion);
+        if destino.enviar(&fila).await? {
+            empleadores.push(fila);
+        }
+    }
+    notificar_carga(INFO, "Empleadores");
+
+    let mut instituciones = Vec::with_capacity(cantidad("Instituciones"));
+    nombre_universidades.shuffle(&mut rng);
+    for nombre in nombre_universidades
+        .iter()
+        .choose_multiple(&mut rng, cantidad("Instituciones"))
+    {
+        let Some(direccion) = direcciones.choose(&mut rng) else {
+            break;
+        };
+        let fila = Instituciones::new(direccion, nombre);
+        if destino.enviar(&fila).await? {
+            instituciones.push(fila);
+        }
+    }
+    notificar_carga(INFO, "Instituciones");
+
+    let mut cur_conf = Vec::with_capacity(cantidad("CursosConferencias"));
+    for _ in 1..=cantidad("CursosConferencias") {
+        let Some(institucion) = instituciones.choose(&mut rng) else {
+            break;
+        };
+        let Some(materia) = materias.choose(&mut rng) else {
+            break;
+        };
+        let fila = CursosConferencias::new(institucion, materia);
+        if destino.enviar(&fila).await? {
+            cur_conf.push(fila);
+        }
+    }
+    notificar_carga(INFO, "CursosConferencias");
+
+    //FIXME: Tiene sentido cargar tantas actividades como muestras?
+    let mut act_uni = Vec::with_capacity(cantidad("ActividadesExtensionUniversitaria"));
+    for _ in 1..=cantidad("ActividadesExtensionUniversitaria") {
+        let Some(institucion) = instituciones.choose(&mut rng) else {
+            break;
+        };
+        let fila = ActividadesExtensionUniversitaria::new(institucion);
+        if destino.enviar(&fila).await? {
+            act_uni.push(fila);
+        }
+    }
+    notificar_carga(INFO, "ActividadesExtensionUniversitaria");
+
+    //FIXME: Tiene sentido cargar tantas actividades como muestras?
+    let mut act_inv = Vec::with_capacity(cantidad("ActividadesInvestigacion"));
+    for _ in 1..=cantidad("ActividadesInvestigacion") {
+        let Some(institucion) = instituciones.choose(&mut rng) else {
+            break;
+        };
+        let Some(categoria) = categorias_investigacion.choose(&mut rng) else {
+            break;
+        };
+        let fila = ActividadesInvestigacion::new(institucion, categoria);
+        if destino.enviar(&fila).await? {
+            act_inv.push(fila);
+        }
+    }
+    notificar_carga(INFO, "ActividadesInvestigacion");
+
+    let mut profesores = Vec::with_capacity(cantidad("Profesores"));
+    for _ in 1..=cantidad("Profesores") {
+        let Some(empleador) = empleadores.choose(&mut rng) else {
+            break;
+        };
+        let fila = Profesores::new(empleador);
+        if destino.enviar(&fila).await? {
+            profesores.push(fila);
+        }
+    }
+    notificar_carga(INFO, "Profesores");
+
+    let autorias = repartir_autorias(
+        profesores.len(),
+        cantidad("Publicaciones"),
+        opciones,
+        &mut rng,
+    );
+    let mut publicaciones = Vec::with_capacity(autorias.len());
+    // Autores de cada publicación aceptada, en el mismo orden que `publicaciones`.
+    let mut autores = Vec::with_capacity(autorias.len());
+    for mut autoria in autorias {
+        // Los profesores cuyos nombres no entran en la columna Autores tampoco van a Publico.
+        let profesores_autores: Vec<&Profesores> =
+            autoria.iter().map(|&p| &profesores[p]).collect();
+        autoria.truncate(Publicaciones::autores_que_entran(&profesores_autores));
+        let fila = Publicaciones::new(&profesores_autores[..autoria.len()]);
+        if destino.enviar(&fila).await? {
+            publicaciones.push(fila);
+            autores.push(autoria);
+        }
+    }
+    notificar_carga(INFO, "Publicaciones");
+
+    let mut contactos: Vec<Contactos> = Vec::with_capacity(profesores.len());
+    let asignacion = distribuciones.asignar(
+        "Contactos",
+        Distribucion::Fija { min: 1, max: 1 },
+        profesores.len(),
+        profesores.len(),
+        &mut rng,
+    );
+    {
+        // Un profesor no puede repetir el tipo y medio de contacto, por lo que se reintenta
+        // algunas veces antes de descartar el contacto.
+        let mut usados = std::collections::HashSet::new();
+        for p in cardinalidades.aplicar("Contactos", asignacion, profesores.len(), &mut rng) {
+            let prof = &profesores[p];
+            let fila = (0..10)
+                .map(|_| Contactos::new(prof))
+                .find(|c| !usados.contains(&(p, c.tipo.clone(), c.medio.clone())));
+            if let Some(fila) = fila {
+                usados.insert((p, fila.tipo.clone(), fila.medio.clone()));
+                if destino.enviar(&fila).await? {
+                    contactos.push(fila);
+                }
+            }
+        }
+    }
+    notificar_carga(INFO, "Contactos");
+
+    let mut dep_emp = Vec::with_capacity(cantidad("DependenciasEmpresas"));
+    let asignacion = distribuciones.asignar(
+        "DependenciasEmpresas",
+        Distribucion::Uniforme,
+        profesores.len(),
+        cantidad("DependenciasEmpresas"),
+        &mut rng,
+    );
+    for p in cardinalidades.aplicar(
+        "DependenciasEmpresas",
+        asignacion,
+        profesores.len(),
+        &mut rng,
+    ) {
+        let Some(obra) = obras_sociales.choose(&mut rng) else {
+            break;
+        };
+        let Some(direccion) = direcciones.choose(&mut rng) else {
+            break;
+        };
+        let profesor = &profesores[p];
+        let fila = DependenciasEmpresas::new(profesor, direccion, obra);
+        if destino.enviar(&fila).await? {
+            dep_emp.push(fila);
+        }
+    }
+    notificar_carga(INFO, "DependenciasEmpresas");
+
+    let mut familiares = Vec::with_capacity(cantidad("Familiares"));
+    let asignacion = distribuciones.asignar(
+        "Familiares",
+        Distribucion::Uniforme,
+        profesores.len(),
+        cantidad("Familiares"),
+        &mut rng,
+    );
+    for p in cardinalidades.aplicar("Familiares", asignacion, profesores.len(), &mut rng) {
+        let Some(direccion) = direcciones.choose(&mut rng) else {
+            break;
+        };
+        let profesor = &profesores[p];
+        let fila = Familiares::new(direccion, profesor);
+        if destino.enviar(&fila).await? {
+            familiares.push(fila);
+        }
+    }
+    notificar_carga(INFO, "Familiares");
+
+    let mut doc_obras = Vec::with_capacity(cantidad("DocObraSocial"));
+    let asignacion = distribuciones.asignar(
+        "DocObraSocial",
+        Distribucion::Uniforme,
+        profesores.len(),
+        cantidad("DocObraSocial"),
+        &mut rng,
+    );
+    for p in cardinalidades.aplicar("DocObraSocial", asignacion, profesores.len(), &mut rng) {
+        let Some(obra) = obras_sociales.choose(&mut rng) else {
+            break;
+        };
+        let profesor = &profesores[p];
+        let fila = DocObraSocial::new(profesor, obra);
+        if destino.enviar(&fila).await? {
+            doc_obras.push(fila);
+        }
+    }
+    notificar_carga(INFO, "DocObraSocial");
+
+    let mut dec_jur = Vec::with_capacity(cantidad("DeclaracionesJuradas"));
+    let asignacion = distribuciones.asignar(
+        "DeclaracionesJuradas",
+        Distribucion::Uniforme,
+        profesores.len(),
+        cantidad("DeclaracionesJuradas"),
+        &mut rng,
+    );
+    for p in cardinalidades.aplicar(
+        "DeclaracionesJuradas",
+        asignacion,
+        profesores.len(),
+        &mut rng,
+    ) {
+        let profesor = &profesores[p];
+        let fila = DeclaracionesJuradas::new(profesor);
+        if destino.enviar(&fila).await? {
+            dec_jur.push(fila);
+        }
+    }
+    notificar_carga(INFO, "DeclaracionesJuradas");
+
+    let mut dec_car = Vec::with_capacity(cantidad("DeclaracionesDeCargo"));
+    for _ in 1..=cantidad("DeclaracionesDeCargo") {
+        let Some(dep) = dep_emp.choose(&mut rng) else {
+            break;
+        };
+        let fila = DeclaracionesDeCargo::new(dep);
+        if destino.enviar(&fila).await? {
+            dec_car.push(fila);
+        }
+    }
+    notificar_carga(INFO, "DeclaracionesDeCargo");
+
+    let mut ant_pro = Vec::with_capacity(cantidad("AntecedentesProfesionales"));
+    for _ in 1..=cantidad("AntecedentesProfesionales") {
+        let Some(profesor) = profesores.choose(&mut rng) else {
+            break;
+        };
+        let Some(declaracion) = dec_car.choose(&mut rng) else {
+            break;
+        };
+        let fila = AntecedentesProfesionales::new(profesor, declaracion);
+        if destino.enviar(&fila).await? {
+            ant_pro.push(fila);
+        }
+    }
+    notificar_carga(INFO, "AntecedentesProfesionales");
+
+    let mut ant_doc = Vec::with_capacity(cantidad("AntecedentesDocentes"));
+    for _ in 1..=cantidad("AntecedentesDocentes") {
+        let Some(institucion) = instituciones.choose(&mut rng) else {
+            break;
+        };
+        let Some(profesor) = profesores.choose(&mut rng) else {
+            break;
+        };
+        let Some(declaracion) = dec_car.choose(&mut rng) else {
+            break;
+        };
+        let fila = AntecedentesDocentes::new(profesor, institucion, declaracion);
+        if destino.enviar(&fila).await? {
+            ant_doc.push(fila);
+        }
+    }
+    notificar_carga(INFO, "AntecedentesDocentes");
+
+    let mut horarios = Vec::with_capacity(cantidad("Horarios"));
+    // Horarios ya ocupados de cada declaración en cada día, que no deben superponerse.
+    let mut ocupados: HashMap<(u32, String), Vec<(time::Time, time::Time)>> = HashMap::new();
+    for _ in 1..=cantidad("Horarios") {
+        let Some(declaraciones) = dec_car.choose(&mut rng) else {
+            break;
+        };
+        let Some(materia) = materias.choose(&mut rng) else {
+            break;
+        };
+        // Si el día ya está muy ocupado se reintenta unas pocas veces y luego se omite la fila.
+        let Some(fila) = (0..10)
+            .map(|_| Horarios::new(declaraciones, materia))
+            .find(|fila| {
+                ocupados
+                    .get(&(fila.id_declaracion, fila.dia.clone()))
+                    .into_iter()
+                    .flatten()
+                    .all(|&(inicio, fin)| fila.hora_fin <= inicio || fin <= fila.hora_inicio)
+            })
+        else {
+            continue;
+        };
+
+        if destino.enviar(&fila).await? {
+            ocupados
+                .entry((fila.id_declaracion, fila.dia.clone()))
+                .or_default()
+                .push((fila.hora_inicio, fila.hora_fin));
+            horarios.push(fila);
+        }
+    }
+    notificar_carga(INFO, "Horarios");
+
+    let asignacion = distribuciones.asignar(
+        "AtendioA",
+        Distribucion::Fija { min: 1, max: 1 },
+        profesores.len(),
+        profesores.len(),
+        &mut rng,
+    );
+    let mut atendio_a = Vec::with_capacity(profesores.len());
+    for p in cardinalidades.aplicar("AtendioA", asignacion, profesores.len(), &mut rng) {
+        let Some(curso) = cur_conf.choose(&mut rng) else {
+            break;
+        };
+        let fila = AtendioA::new(curso, &profesores[p]);
+        if destino.enviar(&fila).await? {
+            atendio_a.push(fila);
+        }
+    }
+    notificar_carga(INFO, "AtendioA");
+    //cargar_atendio_a(&cur_conf, &profesores, conexion)
+
+    let mut conoce_idioma = Vec::with_capacity(profesores.len() * 2);
+    for prof in &profesores {
+        let fila = ConoceIdioma::nativo(prof);
+        if destino.enviar(&fila).await? {
+            conoce_idioma.push(fila);
+        }
+        for _ in 1..=rng.gen_range(1..3) {
+            let Some(idioma) = idiomas.choose(&mut rng) else {
+                break;
+            };
+            let certificacion = certificaciones
+                .iter()
+                .filter(|c| c.idioma == idioma.nombre)
+                .choose(&mut rng);
+            let fila = ConoceIdioma::new(idioma, prof, certificacion);
+            if destino.enviar(&fila).await? {
+                conoce_idioma.push(fila);
+            }
+        }
+    }
+    notificar_carga(INFO, "ConoceIdiomas");
+    //cargar_conoce_idiomas(&idiomas, &profesores, conexion),
+
+    let mut se_da_idioma = Vec::with_capacity(instituciones.len());
+    for inst in &instituciones {
+        for _ in 1..=rng.gen_range(1..3) {
+            let Some(idioma) = idiomas.choose(&mut rng) else {
+                break;
+            };
+            let fila = SeDaIdioma::new(idioma, inst);
+            if destino.enviar(&fila).await? {
+                se_da_idioma.push(fila);
+            }
+        }
+    }
+    notificar_carga(INFO, "SeDaIdiomas");
+    //cargar_se_da_idiomas(&idiomas, &instituciones, conexion),
+
+    let mut beneficia = Vec::with_capacity(cantidad("Beneficia"));
+    for _ in 1..=cantidad_parcial(cantidad("Beneficia"), &mut rng) {
+        let Some(obra) = obras_sociales.choose(&mut rng) else {
+            break;
+        };
+        let Some(familiar) = familiares.choose(&mut rng) else {
+            break;
+        };
+        let fila = Beneficia::new(obra, familiar);
+        if destino.enviar(&fila).await? {
+            beneficia.push(fila);
+        }
+    }
+    notificar_carga(INFO, "Beneficia");
+    //cargar_beneficia(&obras_sociales, &familiares, muestras, conexion),
+
+    let mut posee_titulo = Vec::with_capacity(profesores.len());
+    {
+        let (terciarios, otros): (Vec<Titulos>, Vec<Titulos>) = titulos
+            .iter()
+            .cloned()
+            .partition(|x| x.nivel == "Terciario");
+
+        for prof in &profesores {
+            let Some(t) = terciarios.choose(&mut rng) else {
+                break;
+            };
+            let fila = PoseeTitulo::new(t, prof);
+            if destino.enviar(&fila).await? {
+                posee_titulo.push(fila);
+            }
+        }
+        for _ in 0..rng.gen_range(0..muestras.max(1)) {
+            // Con un solo título no hay títulos de otros niveles.
+            let Some(t) = otros.choose(&mut rng) else {
+                break;
+            };
+            let Some(prof) = profesores.choose(&mut rng) else {
+                break;
+            };
+            let fila = PoseeTitulo::new(t, prof);
+            if destino.enviar(&fila).await? {
+                posee_titulo.push(fila);
+            }
+        }
+        notificar_carga(INFO, "PoseeTitulo");
+    }
+    //cargar_posee_titulo(&titulos, &profesores, muestras, conexion),
+
+    let mut se_da_titulo = Vec::with_capacity(instituciones.len());
+    for inst in &instituciones {
+        for _ in 1..=rng.gen_range(1..5) {
+            let Some(titulo) = titulos.choose(&mut rng) else {
+                break;
+            };
+            let fila = SeDaTitulo::new(titulo, inst);
+            if destino.enviar(&fila).await? {
+                se_da_titulo.push(fila);
+            }
+        }
+    }
+    notificar_carga(INFO, "SeDaTitulo");
+    //cargar_se_da_titulo(&titulos, &instituciones, conexion),
+
+    let mut realiza_inves = Vec::with_capacity(cantidad("RealizaInves"));
+    for _ in 1..=cantidad_parcial(cantidad("RealizaInves"), &mut rng) {
+        let Some(act) = act_inv.choose(&mut rng) else {
+            break;
+        };
+        let Some(prof) = profesores.choose(&mut rng) else {
+            break;
+        };
+        let fila = RealizaInves::new(act, prof);
+        if destino.enviar(&fila).await? {
+            realiza_inves.push(fila);
+        }
+    }
+    notificar_carga(INFO, "RealizaInvestigacion");
+    //cargar_realiza_investigacion(&act_inv, &profesores, muestras, conexion),
+
+    let mut realizo_act = Vec::with_capacity(cantidad("RealizoAct"));
+    for _ in 1..=cantidad_parcial(cantidad("RealizoAct"), &mut rng) {
+        let Some(act) = act_uni.choose(&mut rng) else {
+            break;
+        };
+        let Some(prof) = profesores.choose(&mut rng) else {
+            break;
+        };
+        let fila = RealizoAct::new(act, prof);
+        if destino.enviar(&fila).await? {
+            realizo_act.push(fila);
+        }
+    }
+    notificar_carga(INFO, "RealizoActividad");
+    //cargar_realizo_actividad(&act_uni, &profesores, muestras, conexion),
+
+    let anios: Vec<i32> = publicaciones.iter().map(|p| p.anio).collect();
+    let citas = opciones.citas.generar(&anios, &mut rng);
+    let mut referencia_bibliografica = Vec::with_capacity(citas.len());
+    for (fuente, citador) in citas {
+        let fila = ReferenciaBibliografica::new(&publicaciones[fuente], &publicaciones[citador]);
+        if destino.enviar(&fila).await? {
+            referencia_bibliografica.push(fila);
+        }
+    }
+    notificar_carga(INFO, "ReferenciasBibliograficas");
+    //cargar_referencias_bibliograficas(&publicaciones, conexion),
+
+    let mut publico = Vec::with_capacity(autores.iter().map(Vec::len).sum());
+    for (publicacion, autores) in publicaciones.iter().zip(&autores) {
+        for &p in autores {
+            let fila = Publico::new(publicacion, &profesores[p]);
+            if destino.enviar(&fila).await? {
+                publico.push(fila);
+            }
+        }
+    }
+    notificar_carga(INFO, "Publico");
+    //cargar_publico(&publicaciones, &profesores, conexion),
+
+    let mut participo_en_reunion = Vec::with_capacity(reuniones.len());
+    for r in &reuniones {
+        let Some(profesor) = profesores.choose(&mut rng) else {
+            break;
+        };
+        let fila = ParticipoEnReunion::new(r, profesor);
+        if destino.enviar(&fila).await? {
+            participo_en_reunion.push(fila);
+        }
+    }
+    notificar_carga(INFO, "ParticipoEnReunion");
+    //cargar_participo_en_reunion(&reuniones, &profesores, conexion),
+
+    let mut percibe_en = Vec::with_capacity(percepciones.len());
+    for p in &percepciones {
+        let Some(profesor) = profesores.choose(&mut rng) else {
+            break;
+        };
+        let fila = PercibeEn::new(p, profesor);
+        if destino.enviar(&fila).await? {
+            percibe_en.push(fila);
+        }
+    }
+    notificar_carga(INFO, "PercibeEn");
+    //cargar_percibe_en(&percepciones, &profesores, conexion),
+
+    let asignacion = distribuciones.asignar(
+        "ResideEn",
+        Distribucion::Fija { min: 1, max: 1 },
+        profesores.len(),
+        profesores.len(),
+        &mut rng,
+    );
+    let mut reside_en = Vec::with_capacity(profesores.len());
+    for p in cardinalidades.aplicar("ResideEn", asignacion, profesores.len(), &mut rng) {
+        let Some(dir) = direcciones.choose(&mut rng) else {
+            break;
+        };
+        let fila = ResideEn::new(&profesores[p], dir);
+        if destino.enviar(&fila).await? {
+            reside_en.push(fila);
+        }
+    }
+    notificar_carga(INFO, "ResideEn");
+    //cargar_reside_en(&profesores, &direcciones, conexion),
+
+    let mut asegura_a = Vec::with_capacity(seguros.len());
+    for s in &seguros {
+        let Some(familiar) = familiares.choose(&mut rng) else {
+            break;
+        };
+        let fila = AseguraA::new(s, familiar);
+        if destino.enviar(&fila).await? {
+            asegura_a.push(fila);
+        }
+    }
+    notificar_carga(INFO, "AseguraA");
+    //cargar_asegura_a(&seguros, &familiares, conexion),
+
+    destino.terminar().await?;
+    Ok(Generados {
+        direcciones,
+        titulos,
+        publicaciones,
+        reuniones_cientificas: reuniones,
+        percepciones,
+        seguros,
+        obras_sociales,
+        idiomas: idiomas_cargados,
+        empleadores,
+        instituciones,
+        cursos_conferencias: cur_conf,
+        actividades_extension_universitaria: act_uni,
+        actividades_investigacion: act_inv,
+        profesores,
+        contactos,
+        dependencias_empresas: dep_emp,
+        familiares,
+        doc_obra_social: doc_obras,
+        declaraciones_juradas: dec_jur,
+        declaraciones_de_cargo: dec_car,
+        antecedentes_profesionales: ant_pro,
+        antecedentes_docentes: ant_doc,
+        horarios,
+        atendio_a,
+        conoce_idioma,
+        se_da_idioma,
+        beneficia,
+        posee_titulo,
+        se_da_titulo,
+        realiza_inves,
+        realizo_act,
+        referencia_bibliografica,
+        publico,
+        participo_en_reunion,
+        percibe_en,
+        reside_en,
+        asegura_a,
+    })
+}
@@ -0,0 +1,115 @@
+// Autor: Acosta Quintana, Lautaro
+//! Red de citas entre publicaciones.
+//!
+//! Las referencias bibliográficas forman un grafo dirigido sin ciclos: cada publicación cita sólo
+//! a publicaciones de años anteriores, nunca a sí misma ni dos veces a la misma. Las fuentes se
+//! eligen por conexión preferencial (modelo de Price): la probabilidad de citar una publicación es
+//! proporcional a las citas que ya recibió más la [`Citas::atraccion`], así que unas pocas
+//! publicaciones concentran la mayoría de las citas.
+
+use std::{collections::HashSet, fmt};
+
+use rand::Rng;
+
+/// Cantidad de referencias de cada publicación y cuánto pesan las citas ya recibidas al elegir
+/// las fuentes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Citas {
+    /// Cantidad mínima de publicaciones que cita cada publicación, si hay suficientes anteriores.
+    pub min: usize,
+    /// Cantidad máxima de publicaciones que cita cada publicación.
+    pub max: usize,
+    /// Peso de una publicación sin citas. Con valores chicos las citas se concentran en las
+    /// publicaciones más citadas; con valores grandes se reparten casi uniformemente.
+    pub atraccion: f64,
+}
+
+impl Default for Citas {
+    fn default() -> Self {
+        Self {
+            min: 0,
+            max: 4,
+            atraccion: 1.0,
+        }
+    }
+}
+
+impl Citas {
+    /// Genera las citas entre publicaciones de los años `anios`, como pares de índices
+    /// `(fuente, citador)` en `anios`, donde la fuente siempre es de un año anterior al citador.
+    pub fn generar<R: Rng + ?Sized>(&self, anios: &[i32], rng: &mut R) -> Vec<(usize, usize)> {
+        let mut orden: Vec<usize> = (0..anios.len()).collect();
+        orden.sort_by_key(|&i| anios[i]);
+
+        let mut citas: Vec<(usize, usize)> = Vec::new();
+        // Cantidad de publicaciones, en `orden`, de años anteriores al del citador actual.
+        let mut anteriores = 0;
+        for (posicion, &citador) in orden.iter().enumerate() {
+            while anios[orden[anteriores]] < anios[citador] {
+                anteriores += 1;
+            }
+            debug_assert!(anteriores <= posicion);
+            let cantidad = rng.gen_range(self.min..=self.max).min(anteriores);
+            if cantidad == 0 {
+                continue;
+            }
+
+            // Todas las citas previas son de publicaciones de años anteriores o iguales al del
+            // citador, así que sus fuentes son anteriores al citador. Elegir una cita al azar
+            // elige su fuente con probabilidad proporcional a las citas que recibió, y con
+            // probabilidad `atraccion * anteriores / (citas + atraccion * anteriores)` se elige en
+            // cambio una publicación anterior cualquiera.
+            let uniforme = self.atraccion * anteriores as f64;
+            let previas = citas.len();
+            let mut fuentes = HashSet::with_capacity(cantidad);
+            // Con pocas publicaciones anteriores la misma fuente puede salir muchas veces; se
+            // limitan los intentos en lugar de repetir hasta completar la cantidad.
+            for _ in 0..cantidad * 10 {
+                if fuentes.len() == cantidad {
+                    break;
+                }
+                let fuente =
+                    if previas == 0 || rng.gen::<f64>() * (previas as f64 + uniforme) < uniforme {
+                        orden[rng.gen_range(0..anteriores)]
+                    } else {
+                        citas[rng.gen_range(0..previas)].0
+                    };
+                if fuentes.insert(fuente) {
+                    citas.push((fuente, citador));
+                }
+            }
+        }
+        citas
+    }
+}
+
+impl fmt::Display for Citas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.min, self.max)
+    }
+}
+
+/// Interpreta una cantidad de referencias por publicación de la forma `<min>..<max>`.
+pub fn parsear_referencias(s: &str) -> Result<(usize, usize), String> {
+    let (min, max) = s
+        .split_once("..")
+        .ok_or_else(|| format!("Se esperaba '<min>..<max>' en '{s}'."))?;
+    let entero = |v: &str| {
+        v.trim()
+            .parse::<usize>()
+            .map_err(|_| format!("'{v}' no es un entero válido en '{s}'."))
+    };
+    let (min, max) = (entero(min)?, entero(max)?);
+    if max < min {
+        return Err(format!("El mínimo de '{s}' es mayor al máximo."));
+    }
+    Ok((min, max))
+}
+
+/// Interpreta la [`Citas::atraccion`], que debe ser un número finito no negativo.
+pub fn parsear_atraccion(s: &str) -> Result<f64, String> {
+    match s.trim().parse::<f64>() {
+        Ok(v) if v.is_finite() && v >= 0.0 => Ok(v),
+        _ => Err(format!("'{s}' no es un número finito mayor o igual a 0.")),
+    }
+}
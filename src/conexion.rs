@@ -0,0 +1,248 @@
+// Autor: Acosta Quintana, Lautaro
+use std::{future::Future, path::PathBuf, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, bail};
+use dbdata::Conexion;
+use sqlx::{
+    mysql::{MySqlConnectOptions, MySqlSslMode},
+    pool::PoolOptions,
+    postgres::{PgConnectOptions, PgSslMode},
+    sqlite::SqliteConnectOptions,
+    Database, MySql, Pool,
+};
+
+/// Motores de base de datos en los que se pueden cargar los datos.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Motor {
+    #[default]
+    #[value(name = "mysql")]
+    MySql,
+    Postgres,
+    /// Un archivo de SQLite, o una base de datos en memoria con `sqlite::memory:`.
+    Sqlite,
+}
+
+/// Perfiles que leen la URL de la base de datos de su propio archivo de variables de ambiente.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Perfil {
+    /// Lee `.env.test`.
+    Test,
+    /// Lee `.env.dev`.
+    Dev,
+}
+
+impl Perfil {
+    pub fn archivo(&self) -> &'static str {
+        match self {
+            Perfil::Test => ".env.test",
+            Perfil::Dev => ".env.dev",
+        }
+    }
+}
+
+/// Cifrado de la conexión con el servidor. No tiene efecto en SQLite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ModoTls {
+    /// No cifra la conexión.
+    Deshabilitado,
+    /// Cifra la conexión si el servidor lo permite.
+    Preferido,
+    /// Falla si el servidor no permite cifrar la conexión.
+    Requerido,
+    /// Además verifica el certificado del servidor.
+    VerificarCa,
+    /// Además verifica que el certificado corresponda al host.
+    VerificarIdentidad,
+}
+
+impl From<ModoTls> for MySqlSslMode {
+    fn from(modo: ModoTls) -> Self {
+        match modo {
+            ModoTls::Deshabilitado => MySqlSslMode::Disabled,
+            ModoTls::Preferido => MySqlSslMode::Preferred,
+            ModoTls::Requerido => MySqlSslMode::Required,
+            ModoTls::VerificarCa => MySqlSslMode::VerifyCa,
+            ModoTls::VerificarIdentidad => MySqlSslMode::VerifyIdentity,
+        }
+    }
+}
+
+impl From<ModoTls> for PgSslMode {
+    fn from(modo: ModoTls) -> Self {
+        match modo {
+            ModoTls::Deshabilitado => PgSslMode::Disable,
+            ModoTls::Preferido => PgSslMode::Prefer,
+            ModoTls::Requerido => PgSslMode::Require,
+            ModoTls::VerificarCa => PgSslMode::VerifyCa,
+            ModoTls::VerificarIdentidad => PgSslMode::VerifyFull,
+        }
+    }
+}
+
+/// Parámetros para conectarse con la base de datos.
+#[derive(Debug, Clone)]
+pub struct ConfigConexion {
+    /// URL de la base de datos. Si no se indica se usa la variable `DATABASE_URL`.
+    pub url: Option<String>,
+    /// Perfil del cual leer las variables de ambiente. Si no se indica se lee `.env`, si existe.
+    pub perfil: Option<Perfil>,
+    pub max_conexiones: u32,
+    /// Tiempo máximo para establecer la primera conexión.
+    pub timeout_conexion: Duration,
+    /// Tiempo máximo de espera para obtener una conexión del pool.
+    pub timeout_adquisicion: Duration,
+    /// Si no se indica se usa el de la URL.
+    pub tls: Option<ModoTls>,
+    /// Socket de Unix con el cual conectarse en lugar de usar TCP.
+    pub socket: Option<PathBuf>,
+    /// No crea el archivo de SQLite si no existe y lo abre en modo de sólo lectura.
+    pub solo_lectura: bool,
+}
+
+impl Default for ConfigConexion {
+    fn default() -> Self {
+        Self {
+            url: None,
+            perfil: None,
+            max_conexiones: 10,
+            timeout_conexion: Duration::from_secs(10),
+            timeout_adquisicion: Duration::from_secs(4),
+            tls: None,
+            socket: None,
+            solo_lectura: false,
+        }
+    }
+}
+
+/// Reemplaza la contraseña de `url` para poder mostrarla en los mensajes de error.
+fn ocultar_contrasena(url: &str) -> String {
+    let Some((esquema, resto)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    // La contraseña puede tener '@', así que el host empieza después del último.
+    match resto.rsplit_once('@') {
+        Some((credenciales, host)) if credenciales.contains(':') => {
+            let usuario = credenciales.split(':').next().unwrap_or_default();
+            format!("{esquema}://{usuario}:***@{host}")
+        }
+        _ => url.to_string(),
+    }
+}
+
+impl ConfigConexion {
+    /// Devuelve la URL indicada con `url` o, si no se indicó, la variable `DATABASE_URL` del
+    /// ambiente, leyendo antes el archivo del perfil.
+    pub fn url(&self) -> anyhow::Result<String> {
+        if let Some(url) = &self.url {
+            return Ok(url.clone());
+        }
+
+        match self.perfil {
+            Some(perfil) => {
+                dotenvy::from_filename(perfil.archivo()).map_err(|e| {
+                    anyhow!(
+                        "No se pudo leer el archivo '{}' del perfil: {e}",
+                        perfil.archivo()
+                    )
+                })?;
+            }
+            None => match dotenvy::dotenv() {
+                Ok(_) => {}
+                Err(e) if e.not_found() => {}
+                Err(e) => bail!("No se pudo leer el archivo '.env': {e}"),
+            },
+        }
+
+        std::env::var("DATABASE_URL").map_err(|_| {
+            anyhow!(
+                "No se indicó la URL de la base de datos. Use '--database-url', la variable \
+                'DATABASE_URL' o declárela en el archivo {}.",
+                self.perfil.map_or(".env", |p| p.archivo())
+            )
+        })
+    }
+
+    fn pool<DB: Database>(&self) -> PoolOptions<DB> {
+        PoolOptions::new()
+            .max_connections(self.max_conexiones)
+            .acquire_timeout(self.timeout_adquisicion)
+    }
+
+    /// Espera a que se establezca la conexión con `url` a lo sumo `timeout_conexion`.
+    async fn esperar<T>(
+        &self,
+        url: &str,
+        conexion: impl Future<Output = Result<T, sqlx::Error>>,
+    ) -> anyhow::Result<T> {
+        let url = ocultar_contrasena(url);
+        match tokio::time::timeout(self.timeout_conexion, conexion).await {
+            Ok(resultado) => resultado.map_err(|e| anyhow!("No se pudo conectar con '{url}': {e}")),
+            Err(_) => bail!(
+                "Se agotó el tiempo de espera de {}s al conectar con '{url}'.",
+                self.timeout_conexion.as_secs_f32()
+            ),
+        }
+    }
+}
+
+fn url_invalida(url: &str, e: sqlx::Error) -> anyhow::Error {
+    anyhow!("La URL '{}' no es válida: {e}", ocultar_contrasena(url))
+}
+
+/// Establece una conexión con la base de datos MySQL.
+pub async fn conectar_con_bd(config: &ConfigConexion) -> anyhow::Result<Pool<MySql>> {
+    conectar_mysql(config, &config.url()?).await
+}
+
+async fn conectar_mysql(config: &ConfigConexion, url: &str) -> anyhow::Result<Pool<MySql>> {
+    let mut opciones = MySqlConnectOptions::from_str(url).map_err(|e| url_invalida(url, e))?;
+    if let Some(tls) = config.tls {
+        opciones = opciones.ssl_mode(tls.into());
+    }
+    if let Some(socket) = &config.socket {
+        opciones = opciones.socket(socket);
+    }
+    config
+        .esperar(url, config.pool().connect_with(opciones))
+        .await
+}
+
+/// Establece una conexión con una base de datos del `motor` indicado.
+pub async fn conectar(motor: Motor, config: &ConfigConexion) -> anyhow::Result<Conexion> {
+    let url = config.url()?;
+    Ok(match motor {
+        Motor::MySql => Conexion::MySql(conectar_mysql(config, &url).await?),
+        Motor::Postgres => {
+            let mut opciones =
+                PgConnectOptions::from_str(&url).map_err(|e| url_invalida(&url, e))?;
+            if let Some(tls) = config.tls {
+                opciones = opciones.ssl_mode(tls.into());
+            }
+            if let Some(socket) = &config.socket {
+                opciones = opciones.socket(socket);
+            }
+            Conexion::Postgres(
+                config
+                    .esperar(&url, config.pool().connect_with(opciones))
+                    .await?,
+            )
+        }
+        Motor::Sqlite => {
+            let opciones = SqliteConnectOptions::from_str(&url)
+                .map_err(|e| url_invalida(&url, e))?
+                .create_if_missing(!config.solo_lectura)
+                .read_only(config.solo_lectura)
+                .foreign_keys(true);
+            let mut pool = config.pool();
+            if url.contains(":memory:") {
+                // La base de datos en memoria se pierde al cerrarse la última conexión, por lo
+                // que se usa una sola que nunca se cierra.
+                pool = pool
+                    .max_connections(1)
+                    .idle_timeout(None)
+                    .max_lifetime(None);
+            }
+            Conexion::Sqlite(config.esperar(&url, pool.connect_with(opciones)).await?)
+        }
+    })
+}
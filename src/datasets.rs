@@ -1,27 +1,500 @@
 // Autor: Acosta Quintana, Lautaro
-use std::{error::Error, path::Path};
-
-use serde::Deserialize;
-
-/// Extrae los datos del dataset y los almacena en un vector String
-pub fn cargar_de_csv<P: AsRef<Path>>(archivo: P) -> Result<Vec<String>, Box<dyn Error>> {
-    let archivo = std::fs::File::open(archivo)?;
-    let buffer = std::io::BufReader::new(archivo);
-    let mut reader = csv::ReaderBuilder::new()
-        .has_headers(false)
-        .flexible(true)
-        .delimiter(b';')
-        .trim(csv::Trim::All)
-        .from_reader(buffer);
-
-    let mut resultado = vec![];
-    for r in reader.records() {
-        let valor = r?;
-        for v in valor.iter() {
-            resultado.push(v.to_string());
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    io::{Cursor, Read},
+    path::PathBuf,
+};
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// Describe un dataset utilizado por el generador junto con la copia que se incluye dentro del
+/// binario.
+#[derive(Debug)]
+pub struct Dataset {
+    /// Nombre del archivo dentro del directorio de datasets.
+    pub archivo: &'static str,
+    /// Encabezado esperado en la primera línea del archivo.
+    pub encabezado: &'static [&'static str],
+    /// Delimitador de campos del archivo.
+    pub delimitador: u8,
+    /// Columnas que identifican a cada fila, como la clave primaria de la tabla que se carga con
+    /// ellas. Dos filas con los mismos valores en estas columnas están repetidas.
+    pub clave: &'static [&'static str],
+    incluido: &'static [u8],
+}
+
+pub const UNIVERSIDADES: Dataset = Dataset {
+    archivo: "universidades.csv",
+    encabezado: &["Nombre"],
+    delimitador: b';',
+    clave: &["Nombre"],
+    incluido: include_bytes!("../datasets/universidades.csv"),
+};
+
+pub const IDIOMAS: Dataset = Dataset {
+    archivo: "idiomas.csv",
+    encabezado: &["Nombre"],
+    delimitador: b';',
+    clave: &["Nombre"],
+    incluido: include_bytes!("../datasets/idiomas.csv"),
+};
+
+pub const PROVINCIAS: Dataset = Dataset {
+    archivo: "provincia_localidad_calles.csv",
+    encabezado: &["localidad_nombre", "nombre", "provincia_nombre"],
+    delimitador: b',',
+    clave: &["localidad_nombre", "nombre", "provincia_nombre"],
+    incluido: include_bytes!("../datasets/provincia_localidad_calles.csv"),
+};
+
+pub const TITULOS: Dataset = Dataset {
+    archivo: "titulos.csv",
+    encabezado: &["Nivel", "Titulo"],
+    delimitador: b';',
+    clave: &["Nivel", "Titulo"],
+    incluido: include_bytes!("../datasets/titulos.csv"),
+};
+
+pub const MATERIAS: Dataset = Dataset {
+    archivo: "materias.csv",
+    encabezado: &["Nombre"],
+    delimitador: b';',
+    clave: &["Nombre"],
+    incluido: include_bytes!("../datasets/materias.csv"),
+};
+
+pub const CATEGORIAS_INVESTIGACION: Dataset = Dataset {
+    archivo: "categorias_investigacion.csv",
+    encabezado: &["Nombre"],
+    delimitador: b';',
+    clave: &["Nombre"],
+    incluido: include_bytes!("../datasets/categorias_investigacion.csv"),
+};
+
+pub const CERTIFICACIONES: Dataset = Dataset {
+    archivo: "certificaciones.csv",
+    encabezado: &["Idioma", "Certificacion", "Nivel"],
+    delimitador: b';',
+    clave: &["Idioma", "Certificacion"],
+    incluido: include_bytes!("../datasets/certificaciones.csv"),
+};
+
+pub const PERCEPCIONES: Dataset = Dataset {
+    archivo: "percepciones.csv",
+    encabezado: &["Tipo", "Regimen", "Causa"],
+    delimitador: b';',
+    clave: &["Tipo"],
+    incluido: include_bytes!("../datasets/percepciones.csv"),
+};
+
+pub const OBRAS_SOCIALES: Dataset = Dataset {
+    archivo: "obras_sociales.csv",
+    encabezado: &["RNOS", "Nombre"],
+    delimitador: b';',
+    clave: &["RNOS"],
+    incluido: include_bytes!("../datasets/obras_sociales.csv"),
+};
+
+pub const ASEGURADORAS: Dataset = Dataset {
+    archivo: "aseguradoras.csv",
+    encabezado: &["Nombre"],
+    delimitador: b';',
+    clave: &["Nombre"],
+    incluido: include_bytes!("../datasets/aseguradoras.csv"),
+};
+
+pub const CAJAS_PREVISIONALES: Dataset = Dataset {
+    archivo: "cajas_previsionales.csv",
+    encabezado: &["Nombre"],
+    delimitador: b';',
+    clave: &["Nombre"],
+    incluido: include_bytes!("../datasets/cajas_previsionales.csv"),
+};
+
+/// Todos los datasets que utiliza el programa.
+pub const DATASETS: [&Dataset; 11] = [
+    &UNIVERSIDADES,
+    &IDIOMAS,
+    &PROVINCIAS,
+    &TITULOS,
+    &MATERIAS,
+    &CATEGORIAS_INVESTIGACION,
+    &CERTIFICACIONES,
+    &PERCEPCIONES,
+    &OBRAS_SOCIALES,
+    &ASEGURADORAS,
+    &CAJAS_PREVISIONALES,
+];
+
+/// Indica de dónde se leen los datasets.
+#[derive(Debug, Default, Clone)]
+pub struct FuenteDatasets {
+    /// Directorio con los datasets. Si no se indica se usan los incluidos en el binario.
+    pub directorio: Option<PathBuf>,
+}
+
+/// Errores que pueden ocurrir al leer un dataset.
+#[derive(Debug)]
+pub enum ErrorDataset {
+    /// Se pidió leer los datasets de un directorio y el archivo no está.
+    Faltante { ruta: PathBuf },
+    /// No se pudo leer el archivo.
+    Lectura { archivo: String, error: String },
+    /// La primera línea no coincide con el encabezado esperado.
+    Encabezado {
+        archivo: String,
+        esperado: Vec<String>,
+        encontrado: Vec<String>,
+    },
+    /// Una línea no se pudo interpretar.
+    Linea {
+        archivo: String,
+        linea: u64,
+        motivo: String,
+    },
+    /// Una línea tiene un campo vacío.
+    Vacio {
+        archivo: String,
+        linea: u64,
+        columna: String,
+    },
+    /// Una línea repite un valor que ya apareció antes en el archivo.
+    Duplicado {
+        archivo: String,
+        linea: u64,
+        primera: u64,
+        valor: String,
+    },
+}
+
+impl fmt::Display for ErrorDataset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorDataset::Faltante { ruta } => write!(
+                f,
+                "No se encontró el dataset '{}'. Verifique la ruta indicada en '--datasets' o ejecute el programa sin ella para usar los datasets incluidos.",
+                ruta.display()
+            ),
+            ErrorDataset::Lectura { archivo, error } => {
+                write!(f, "{archivo}: No se pudo leer el archivo: {error}")
+            }
+            ErrorDataset::Encabezado {
+                archivo,
+                esperado,
+                encontrado,
+            } => write!(
+                f,
+                "{archivo}:1: Encabezado inválido: se esperaba '{}' y se encontró '{}'.",
+                esperado.join(","),
+                encontrado.join(",")
+            ),
+            ErrorDataset::Linea {
+                archivo,
+                linea,
+                motivo,
+            } => write!(f, "{archivo}:{linea}: {motivo}"),
+            ErrorDataset::Vacio {
+                archivo,
+                linea,
+                columna,
+            } => write!(f, "{archivo}:{linea}: La columna '{columna}' está vacía."),
+            ErrorDataset::Duplicado {
+                archivo,
+                linea,
+                primera,
+                valor,
+            } => write!(
+                f,
+                "{archivo}:{linea}: '{valor}' está duplicado (aparece por primera vez en la línea {primera})."
+            ),
+        }
+    }
+}
+
+impl Error for ErrorDataset {}
+
+impl Dataset {
+    /// Abre el dataset desde el directorio de `fuente` si fue indicado, o la copia incluida en el
+    /// binario en caso contrario.
+    pub fn abrir(&self, fuente: &FuenteDatasets) -> Result<Box<dyn Read>, ErrorDataset> {
+        match &fuente.directorio {
+            Some(dir) => {
+                let ruta = dir.join(self.archivo);
+                if !ruta.is_file() {
+                    return Err(ErrorDataset::Faltante { ruta });
+                }
+                let error_lectura = |e: std::io::Error| ErrorDataset::Lectura {
+                    archivo: ruta.display().to_string(),
+                    error: e.to_string(),
+                };
+                // El lector de CSV ya lee el archivo de a partes con su propio búfer.
+                let archivo = std::fs::File::open(&ruta).map_err(error_lectura)?;
+                Ok(Box::new(archivo))
+            }
+            None => Ok(Box::new(Cursor::new(self.incluido))),
+        }
+    }
+
+    /// Describe de dónde se leería el dataset.
+    pub fn origen(&self, fuente: &FuenteDatasets) -> String {
+        match &fuente.directorio {
+            Some(dir) => dir.join(self.archivo).display().to_string(),
+            None => format!("{} (incluido)", self.archivo),
+        }
+    }
+
+    /// Recorre las filas del dataset luego de validar el encabezado, informando a `visitar` el
+    /// número de línea de cada una. Las filas con campos vacíos se rechazan.
+    fn recorrer<F>(&self, fuente: &FuenteDatasets, mut visitar: F) -> Result<(), ErrorDataset>
+    where
+        F: FnMut(u64, &csv::StringRecord) -> Result<(), ErrorDataset>,
+    {
+        let archivo = self.origen(fuente);
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(self.delimitador)
+            .trim(csv::Trim::All)
+            .buffer_capacity(1 << 16)
+            .from_reader(self.abrir(fuente)?);
+
+        let mut registros = reader.records();
+        let encabezado = match registros.next() {
+            Some(r) => r.map_err(|e| error_csv(&archivo, e))?,
+            None => csv::StringRecord::new(),
+        };
+        let encontrado: Vec<String> = encabezado
+            .iter()
+            .map(|c| c.trim_start_matches('\u{feff}').to_string())
+            .collect();
+        if encontrado != self.encabezado {
+            return Err(ErrorDataset::Encabezado {
+                archivo,
+                esperado: self.encabezado.iter().map(|c| c.to_string()).collect(),
+                encontrado,
+            });
+        }
+
+        for registro in registros {
+            let registro = registro.map_err(|e| error_csv(&archivo, e))?;
+            let linea = registro.position().map_or(0, |p| p.line());
+            if let Some(i) = registro.iter().position(str::is_empty) {
+                return Err(ErrorDataset::Vacio {
+                    archivo,
+                    linea,
+                    columna: self.encabezado[i].to_string(),
+                });
+            }
+            visitar(linea, &registro)?;
+        }
+        Ok(())
+    }
+
+    /// Como [`Self::recorrer`], pero además compara la [`Dataset::clave`] de cada fila con las de
+    /// las anteriores. A `visitar` le llega la fila o, si repite la clave de una anterior, el
+    /// [`ErrorDataset::Duplicado`] correspondiente, y decide si corta el recorrido.
+    fn recorrer_sin_repetidas<F>(
+        &self,
+        fuente: &FuenteDatasets,
+        mut visitar: F,
+    ) -> Result<(), ErrorDataset>
+    where
+        F: FnMut(u64, Result<&csv::StringRecord, ErrorDataset>) -> Result<(), ErrorDataset>,
+    {
+        let archivo = self.origen(fuente);
+        let columnas: Vec<usize> = self
+            .clave
+            .iter()
+            .map(|c| {
+                self.encabezado
+                    .iter()
+                    .position(|e| e == c)
+                    .expect("La clave de un dataset debe estar en su encabezado")
+            })
+            .collect();
+        let mut vistos: HashMap<Vec<String>, u64> = HashMap::new();
+
+        self.recorrer(fuente, |linea, registro| {
+            let clave: Vec<String> = columnas.iter().map(|&i| registro[i].to_string()).collect();
+            match vistos.get(&clave) {
+                Some(&primera) => visitar(
+                    linea,
+                    Err(ErrorDataset::Duplicado {
+                        archivo: archivo.clone(),
+                        linea,
+                        primera,
+                        valor: clave.join(","),
+                    }),
+                ),
+                None => {
+                    vistos.insert(clave, linea);
+                    visitar(linea, Ok(registro))
+                }
+            }
+        })
+    }
+
+    /// Lee todas las filas del dataset como valores de tipo `T`, rechazando las que repiten la
+    /// [`Dataset::clave`] de una anterior.
+    pub fn leer<T: DeserializeOwned>(
+        &self,
+        fuente: &FuenteDatasets,
+    ) -> Result<Vec<T>, ErrorDataset> {
+        let archivo = self.origen(fuente);
+        let encabezado = csv::StringRecord::from(self.encabezado.to_vec());
+        let mut filas = Vec::new();
+
+        self.recorrer_sin_repetidas(fuente, |linea, registro| {
+            let fila =
+                registro?
+                    .deserialize(Some(&encabezado))
+                    .map_err(|e| ErrorDataset::Linea {
+                        archivo: archivo.clone(),
+                        linea,
+                        motivo: e.to_string(),
+                    })?;
+            filas.push(fila);
+            Ok(())
+        })?;
+        Ok(filas)
+    }
+
+    /// Revisa que el dataset exista, que su encabezado sea el esperado y que cada fila sea
+    /// válida, contando sus filas.
+    pub fn verificar(&self, fuente: &FuenteDatasets) -> Verificacion {
+        let origen = self.origen(fuente);
+        let mut filas = 0;
+        let mut problemas = Vec::new();
+
+        let resultado = self.recorrer_sin_repetidas(fuente, |_, registro| {
+            filas += 1;
+            // Las filas repetidas se informan todas, sin cortar el recorrido.
+            if let Err(err) = registro {
+                problemas.push(err);
+            }
+            Ok(())
+        });
+
+        if let Err(err) = resultado {
+            problemas.push(err);
+        } else if filas == 0 {
+            problemas.push(ErrorDataset::Linea {
+                archivo: origen.clone(),
+                linea: 2,
+                motivo: "El archivo no contiene filas.".to_string(),
+            });
+        }
+
+        Verificacion {
+            origen,
+            filas,
+            problemas,
         }
     }
-    Ok(resultado)
+}
+
+fn error_csv(archivo: &str, error: csv::Error) -> ErrorDataset {
+    match error.position() {
+        Some(pos) => ErrorDataset::Linea {
+            archivo: archivo.to_string(),
+            linea: pos.line(),
+            motivo: match error.kind() {
+                csv::ErrorKind::UnequalLengths {
+                    expected_len, len, ..
+                } => format!("Se esperaban {expected_len} campos y se encontraron {len}."),
+                _ => error.to_string(),
+            },
+        },
+        None => ErrorDataset::Lectura {
+            archivo: archivo.to_string(),
+            error: error.to_string(),
+        },
+    }
+}
+
+/// Resultado de verificar un dataset.
+#[derive(Debug)]
+pub struct Verificacion {
+    pub origen: String,
+    pub filas: usize,
+    pub problemas: Vec<ErrorDataset>,
+}
+
+impl Verificacion {
+    pub fn es_valido(&self) -> bool {
+        self.problemas.is_empty()
+    }
+}
+
+/// Fila de un dataset que sólo contiene nombres.
+#[derive(Debug, Deserialize)]
+struct FilaNombre {
+    #[serde(rename = "Nombre")]
+    nombre: String,
+}
+
+/// Fila del dataset de calles.
+#[derive(Debug, Deserialize)]
+struct FilaCalle {
+    localidad_nombre: String,
+    nombre: String,
+    provincia_nombre: String,
+}
+
+/// Título universitario del dataset de títulos.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Titulo {
+    /// Uno de 'Terciario', 'Tecnicatura', 'Maestria' o 'Doctorado'.
+    #[serde(rename = "Nivel")]
+    pub nivel: String,
+    #[serde(rename = "Titulo")]
+    pub nombre: String,
+}
+
+/// Certificación de un idioma con el nivel del Marco Común Europeo que acredita.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Certificacion {
+    #[serde(rename = "Idioma")]
+    pub idioma: String,
+    #[serde(rename = "Certificacion")]
+    pub nombre: String,
+    #[serde(rename = "Nivel")]
+    pub nivel: String,
+}
+
+/// Tipo de jubilación, pensión o prestación con su régimen y la causa por la que se otorga.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TipoPercepcion {
+    #[serde(rename = "Tipo")]
+    pub tipo: String,
+    #[serde(rename = "Regimen")]
+    pub regimen: String,
+    #[serde(rename = "Causa")]
+    pub causa: String,
+}
+
+/// Obra social inscripta en el Registro Nacional de Obras Sociales.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ObraSocial {
+    /// Código en el registro, que se usa como `IDObraSocial`.
+    #[serde(rename = "RNOS")]
+    pub rnos: u32,
+    #[serde(rename = "Nombre")]
+    pub nombre: String,
+}
+
+/// Extrae los nombres de un dataset de una sola columna, como `idiomas.csv` o
+/// `materias.csv`.
+pub fn cargar_nombres(
+    dataset: &Dataset,
+    fuente: &FuenteDatasets,
+) -> Result<Vec<String>, ErrorDataset> {
+    Ok(dataset
+        .leer::<FilaNombre>(fuente)?
+        .into_iter()
+        .map(|f| f.nombre)
+        .collect())
 }
 
 /// Modela una Localidad con nombre y las calles que le pertenecen.
@@ -38,48 +511,111 @@ pub struct Provincia {
     pub localidades: Vec<Localidad>,
 }
 
-/// Extrae los datos del dataset y los almacena en un vector String
-pub fn cargar_provincias<P: AsRef<Path>>(archivo: P) -> Result<Vec<Provincia>, Box<dyn Error>> {
-    let archivo = std::fs::File::open(archivo)?;
-    let buffer = std::io::BufReader::new(archivo);
-    let mut reader = csv::ReaderBuilder::new()
-        .has_headers(true)
-        .flexible(true)
-        .delimiter(b',')
-        .trim(csv::Trim::All)
-        .from_reader(buffer);
+/// Posición de una localidad dentro de su provincia y la línea en que apareció cada una de sus
+/// calles.
+type IndiceLocalidad = (usize, HashMap<String, u64>);
+
+/// Extrae las provincias con sus localidades y calles del dataset de calles.
+///
+/// Las provincias y localidades se indexan por nombre mientras se recorre el archivo, por lo que
+/// cada fila se procesa en tiempo constante y el archivo no se guarda completo en memoria.
+pub fn cargar_provincias(
+    dataset: &Dataset,
+    fuente: &FuenteDatasets,
+) -> Result<Vec<Provincia>, ErrorDataset> {
+    let archivo = dataset.origen(fuente);
+    let encabezado = csv::StringRecord::from(dataset.encabezado.to_vec());
 
     let mut provincias: Vec<Provincia> = Vec::new();
+    let mut indice_provincias: HashMap<String, usize> = HashMap::new();
+    // Por cada provincia, el índice de sus localidades.
+    let mut indice_localidades: Vec<HashMap<String, IndiceLocalidad>> = Vec::new();
 
-    for result in reader.deserialize() {
-        let record: csv::Result<(String, String, String)> = result;
-        if let Ok((localidad_nombre, nombre, provincia_nombre)) = record {
-            if let Some(provincia) = provincias.iter_mut().find(|p| p.nombre == provincia_nombre) {
-                if let Some(localidad) = provincia
-                    .localidades
-                    .iter_mut()
-                    .find(|l| l.nombre == localidad_nombre)
-                {
-                    localidad.calles.push(nombre);
-                } else {
-                    let nueva_localidad = Localidad {
-                        nombre: localidad_nombre,
-                        calles: vec![nombre],
-                    };
-                    provincia.localidades.push(nueva_localidad);
-                }
-            } else {
-                let nueva_localidad = Localidad {
-                    nombre: localidad_nombre,
-                    calles: vec![nombre],
-                };
-                let nueva_provincia = Provincia {
+    dataset.recorrer(fuente, |linea, registro| {
+        let FilaCalle {
+            localidad_nombre,
+            nombre,
+            provincia_nombre,
+        } = registro
+            .deserialize(Some(&encabezado))
+            .map_err(|e| ErrorDataset::Linea {
+                archivo: archivo.clone(),
+                linea,
+                motivo: e.to_string(),
+            })?;
+
+        let p = match indice_provincias.get(&provincia_nombre) {
+            Some(&p) => p,
+            None => {
+                indice_provincias.insert(provincia_nombre.clone(), provincias.len());
+                indice_localidades.push(HashMap::new());
+                provincias.push(Provincia {
                     nombre: provincia_nombre,
-                    localidades: vec![nueva_localidad],
-                };
-                provincias.push(nueva_provincia);
+                    localidades: Vec::new(),
+                });
+                provincias.len() - 1
             }
+        };
+        let provincia = &mut provincias[p];
+
+        let (l, calles) = indice_localidades[p]
+            .entry(localidad_nombre.clone())
+            .or_insert_with(|| {
+                provincia.localidades.push(Localidad {
+                    nombre: localidad_nombre,
+                    calles: Vec::new(),
+                });
+                (provincia.localidades.len() - 1, HashMap::new())
+            });
+
+        if let Some(&primera) = calles.get(&nombre) {
+            return Err(ErrorDataset::Duplicado {
+                archivo: archivo.clone(),
+                linea,
+                primera,
+                valor: registro.iter().collect::<Vec<_>>().join(","),
+            });
         }
-    }
+        calles.insert(nombre.clone(), linea);
+        provincia.localidades[*l].calles.push(nombre);
+        Ok(())
+    })?;
+
     Ok(provincias)
 }
+
+/// Contenido de los datasets que se necesitan para generar los datos.
+#[derive(Debug)]
+pub struct Datasets {
+    pub universidades: Vec<String>,
+    pub provincias: Vec<Provincia>,
+    pub idiomas: Vec<String>,
+    pub titulos: Vec<Titulo>,
+    /// Materias, que se usan como nombres de cátedras y temas de cursos.
+    pub materias: Vec<String>,
+    pub categorias_investigacion: Vec<String>,
+    pub certificaciones: Vec<Certificacion>,
+    pub percepciones: Vec<TipoPercepcion>,
+    pub obras_sociales: Vec<ObraSocial>,
+    pub aseguradoras: Vec<String>,
+    pub cajas_previsionales: Vec<String>,
+}
+
+impl Datasets {
+    /// Lee todos los datasets desde `fuente`.
+    pub fn cargar(fuente: &FuenteDatasets) -> Result<Self, ErrorDataset> {
+        Ok(Self {
+            universidades: cargar_nombres(&UNIVERSIDADES, fuente)?,
+            provincias: cargar_provincias(&PROVINCIAS, fuente)?,
+            idiomas: cargar_nombres(&IDIOMAS, fuente)?,
+            titulos: TITULOS.leer(fuente)?,
+            materias: cargar_nombres(&MATERIAS, fuente)?,
+            categorias_investigacion: cargar_nombres(&CATEGORIAS_INVESTIGACION, fuente)?,
+            certificaciones: CERTIFICACIONES.leer(fuente)?,
+            percepciones: PERCEPCIONES.leer(fuente)?,
+            obras_sociales: OBRAS_SOCIALES.leer(fuente)?,
+            aseguradoras: cargar_nombres(&ASEGURADORAS, fuente)?,
+            cajas_previsionales: cargar_nombres(&CAJAS_PREVISIONALES, fuente)?,
+        })
+    }
+}
@@ -1,7 +1,6 @@
 // Autor: Acosta Quintana, Lautaro
-use crate::{incrementar_contador, notificar_carga, Notificacion::*};
-use colored::Colorize;
-use dbdata::DBData;
+use crate::datasets::{Certificacion, TipoPercepcion, Titulo};
+use dbdata::{Ajustable, DBData, Fila, TipoColumna, Valor, ValorColumna, ValorTsv};
 use dbdata_derive::DBData;
 use fake::{
     faker::{
@@ -12,26 +11,40 @@ use fake::{
         lorem::en::*,
         name::en::*,
         phone_number::en::{CellNumber, PhoneNumber},
-        time::en::{Date, Time},
+        time::en::Date,
     },
     Dummy, Fake, Faker,
 };
 use once_cell::sync::Lazy;
 use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
-use sqlx::{
-    types::{time::Date, BigDecimal, Type},
-    MySql, Pool,
-};
-use std::{error::Error, sync::Mutex};
+use sqlx::types::{time::Date, BigDecimal, Type};
+use std::sync::Mutex;
 use time::Duration;
 
 static GLOBAL_RNG: Lazy<Mutex<StdRng>> = Lazy::new(|| Mutex::new(StdRng::from_entropy()));
 
+/// Reinicia el generador con el que se crean las filas a partir de `semilla`.
+pub fn sembrar(semilla: u64) {
+    *GLOBAL_RNG
+        .lock()
+        .expect("Fallo en adquirir el rng dentro del Mutex") = StdRng::seed_from_u64(semilla);
+}
+
+/// Genera una fila al azar con el generador de las filas, ajustada a los tipos de sus columnas.
+pub fn generar<T: Dummy<Faker> + Fila>() -> T {
+    let mut rng = GLOBAL_RNG
+        .lock()
+        .expect("Fallo en adquirir el rng dentro del Mutex");
+    Faker.fake_with_rng::<T, _>(&mut *rng).ajustada()
+}
+
 /// Representa a la Tabla Instituciones.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Instituciones {
+    #[dbdata(varchar(255))]
     pub nombre: String,
     pub codigo_postal: u32, // WARN: FK Direcciones
+    #[dbdata(varchar(100))]
     pub calle: String,
     pub numero: u32,
 }
@@ -48,20 +61,28 @@ impl Instituciones {
             calle,
             numero,
         }
+        .ajustada()
     }
 }
 /// Representa a la tabla Profesores.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Profesores {
+    #[dbdata(char(8))]
     pub dni: Dni,
+    #[dbdata(varchar(100))]
     pub nombre: String,
+    #[dbdata(varchar(100))]
     pub apellido: String,
     pub fecha_nacimiento: Date,
+    #[dbdata(varchar(100))]
     pub nacionalidad: String,
     pub estado_civil: String, // ('Soltero/a', 'Casado/a', 'Divorciado/a', 'Viudo/a', 'Conviviente')
     pub sexo: String,         // ('M', 'F')
+    #[dbdata(char(11))]
     pub cuit: Option<Cuil>,
+    #[dbdata(char(11))]
     pub cuil: Cuil,
+    #[dbdata(char(11))]
     pub cuit_empleador: Cuil, //WARN: FK de Empleador
 }
 
@@ -70,9 +91,9 @@ impl Profesores {
         let mut rng = GLOBAL_RNG
             .lock()
             .expect("Fallo en adquirir el rng dentro del Mutex");
-        let dni = Faker.fake();
-        let nombre = FirstName().fake();
-        let apellido = LastName().fake();
+        let dni = Faker.fake_with_rng(&mut *rng);
+        let nombre = FirstName().fake_with_rng(&mut *rng);
+        let apellido = LastName().fake_with_rng(&mut *rng);
         let estado_civil = [
             "Soltero/a",
             "Casado/a",
@@ -89,7 +110,7 @@ impl Profesores {
             Date::from_ordinal_date(rng.gen_range(1960..=1980), rng.gen_range(1..=30))
                 .expect("Fallo en generar una fecha de nacimiento");
 
-        let nacionalidad = CountryName().fake();
+        let nacionalidad = CountryName().fake_with_rng(&mut *rng);
         let cuil = Cuil::new(&dni);
         let cuit = if rng.gen::<bool>() {
             Some(Cuil::new(&dni))
@@ -110,16 +131,20 @@ impl Profesores {
             cuil,
             cuit_empleador,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla Contactos.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Contactos {
+    #[dbdata(char(8))]
     pub dni_profesor: Dni, //WARN: FK de Profesores
-    pub tipo: String,      // ('Personal', 'Empresarial', 'Otro')
-    pub medio: String,     // ('Celular', 'Telefono', 'Email')
+    pub tipo: String,  // ('Personal', 'Empresarial', 'Otro')
+    pub medio: String, // ('Celular', 'Telefono', 'Email')
+    #[dbdata(varchar(100))]
     pub direccion: Option<String>,
+    #[dbdata(varchar(30))]
     pub numero: Option<String>,
 }
 
@@ -140,12 +165,12 @@ impl Contactos {
             .to_string();
 
         let direccion = match medio.as_str() {
-            "Email" => Some(SafeEmail().fake()),
+            "Email" => Some(SafeEmail().fake_with_rng(&mut *rng)),
             _ => None,
         };
         let numero = match medio.as_str() {
-            "Telefono" => Some(PhoneNumber().fake()),
-            "Celular" => Some(CellNumber().fake()),
+            "Telefono" => Some(PhoneNumber().fake_with_rng(&mut *rng)),
+            "Celular" => Some(CellNumber().fake_with_rng(&mut *rng)),
             _ => None,
         };
 
@@ -162,55 +187,82 @@ impl Contactos {
             direccion,
             numero,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla Titulos.
 #[derive(Debug, DBData, Clone)]
 pub struct Titulos {
+    #[dbdata(varchar(50))]
     pub nivel: String,
+    #[dbdata(varchar(100))]
     pub titulo: String,
 }
 
-impl Dummy<Faker> for Titulos {
-    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
-        let nivel = ["Terciario", "Maestria", "Doctorado", "Tecnicatura"]
-            .choose(rng)
-            .unwrap()
-            .to_string();
-
+impl Titulos {
+    pub fn new(titulo: &Titulo) -> Self {
         Self {
-            nivel,
-            titulo: Words(4..5).fake::<Vec<String>>().join(" "),
+            nivel: titulo.nivel.clone(),
+            titulo: titulo.nombre.clone(),
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla CursosConferencias.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct CursosConferencias {
+    #[dbdata(varchar(255))]
     pub nombre_inst: String,
+    #[dbdata(varchar(100))]
     pub nombre_curso: String,
+    #[dbdata(varchar(255))]
     pub descripcion: Option<String>,
     pub tipo: String, // ('Curso', 'Conferencia')
 }
 
 impl CursosConferencias {
-    pub fn new(institucion: &Instituciones) -> Self {
+    /// Curso o conferencia sobre `materia`. Las conferencias llevan el año en que se realizaron.
+    pub fn new(institucion: &Instituciones, materia: &str) -> Self {
         let mut rng = GLOBAL_RNG
             .lock()
             .expect("Fallo en adquirir el rng dentro del Mutex");
         let nombre_inst = institucion.nombre.clone();
-        let nombre_curso = Words(3..5).fake::<Vec<String>>().join(" ");
-        let descripcion = if rng.gen::<bool>() {
-            Some(Words(1..20).fake::<Vec<String>>().join(" "))
-        } else {
-            None
-        };
         let tipo = ["Curso", "Conferencia"]
             .choose(&mut *rng)
             .unwrap()
             .to_string();
+        let nombre_curso = if tipo == "Curso" {
+            let prefijo = [
+                "Curso de",
+                "Taller de",
+                "Seminario de",
+                "Curso de Posgrado en",
+            ]
+            .choose(&mut *rng)
+            .unwrap();
+            format!("{prefijo} {materia}")
+        } else {
+            let prefijo = [
+                "Jornadas de",
+                "Congreso de",
+                "Simposio de",
+                "Conferencia sobre",
+            ]
+            .choose(&mut *rng)
+            .unwrap();
+            format!("{prefijo} {materia} {}", rng.gen_range(2000..=2024))
+        };
+        let descripcion = if rng.gen::<bool>() {
+            Some(
+                Words(1..20)
+                    .fake_with_rng::<Vec<String>, _>(&mut *rng)
+                    .join(" "),
+            )
+        } else {
+            None
+        };
 
         Self {
             nombre_inst,
@@ -218,15 +270,19 @@ impl CursosConferencias {
             descripcion,
             tipo,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla AntecedentesDocentes.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct AntecedentesDocentes {
+    #[dbdata(varchar(255))]
     pub nombre_inst: String,
+    #[dbdata(varchar(50))]
     pub unidad_academica: String,
     pub id_declaracion: u32,
+    #[dbdata(char(8))]
     pub dni_profesor: Dni, // WARN: FK de Profesores
     pub desde: Date,
     pub hasta: Option<Date>,
@@ -245,7 +301,9 @@ impl AntecedentesDocentes {
         let nombre_inst = institucion.nombre.clone();
         let id_declaracion = declaracion.id_declaracion;
 
-        let unidad_academica = Words(1..3).fake::<Vec<String>>().join(" ");
+        let unidad_academica = Words(1..3)
+            .fake_with_rng::<Vec<String>, _>(&mut *rng)
+            .join(" ");
         let dni_profesor = profesor.dni.clone();
         let desde = profesor.fecha_nacimiento + Duration::days(rng.gen_range(30..50) * 365);
         let hasta = if rng.gen::<bool>() {
@@ -264,68 +322,79 @@ impl AntecedentesDocentes {
             hasta,
             dedicacion,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla ActividadesInvestigacion.
-#[derive(Debug, Dummy, DBData)]
+#[derive(Debug, Clone, Dummy, DBData)]
 pub struct ActividadesInvestigacion {
     pub id_investigacion: u32,
+    #[dbdata(varchar(255))]
     pub nombre_inst: String,
+    #[dbdata(varchar(50))]
     pub categoria: String,
+    #[dbdata(varchar(50))]
     pub area_ppal: String,
 }
 
 impl ActividadesInvestigacion {
-    pub fn new(institucion: &Instituciones) -> Self {
-        let id_investigacion = GLOBAL_RNG
+    pub fn new(institucion: &Instituciones, categoria: &str) -> Self {
+        let mut rng = GLOBAL_RNG
             .lock()
-            .expect("Fallo en adquirir el rng dentro del Mutex")
-            .gen();
+            .expect("Fallo en adquirir el rng dentro del Mutex");
+        let id_investigacion = rng.gen();
         let nombre_inst = institucion.nombre.clone();
-        let categoria = Word().fake();
-        let area_ppal: String = Field().fake();
+        let categoria = categoria.to_string();
+        let area_ppal: String = Field().fake_with_rng(&mut *rng);
         Self {
             id_investigacion,
             nombre_inst,
             categoria,
             area_ppal,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla ActividadesExtensionUniversitaria.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct ActividadesExtensionUniversitaria {
     pub id_actividad: u32,
+    #[dbdata(varchar(255))]
     pub nombre_inst: String,
+    #[dbdata(varchar(50))]
     pub cargo: String,
+    #[dbdata(varchar(50))]
     pub categoria: String,
 }
 
 impl ActividadesExtensionUniversitaria {
     pub fn new(institucion: &Instituciones) -> Self {
-        let id_actividad = GLOBAL_RNG
+        let mut rng = GLOBAL_RNG
             .lock()
-            .expect("Fallo en adquirir el rng dentro del Mutex")
-            .gen();
+            .expect("Fallo en adquirir el rng dentro del Mutex");
+        let id_actividad = rng.gen();
         let nombre_inst = institucion.nombre.clone();
-        let cargo: String = Position().fake();
-        let categoria = Word().fake();
+        let cargo: String = Position().fake_with_rng(&mut *rng);
+        let categoria = Word().fake_with_rng(&mut *rng);
         Self {
             id_actividad,
             nombre_inst,
             cargo,
             categoria,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla AntecedentesProfesionales.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct AntecedentesProfesionales {
+    #[dbdata(char(8))]
     pub dni_profesor: Dni, //WARN: FK de Profesores
     pub id_declaracion: u32,
+    #[dbdata(varchar(50))]
     pub tipo_actividad: String,
     pub desde: Date,
     pub hasta: Date,
@@ -338,7 +407,7 @@ impl AntecedentesProfesionales {
             .expect("Fallo en adquirir el rng dentro del Mutex");
         let dni_profesor = profesor.dni.clone();
         let id_declaracion = declaracion.id_declaracion;
-        let tipo_actividad = Word().fake();
+        let tipo_actividad = Word().fake_with_rng(&mut *rng);
         let desde = profesor.fecha_nacimiento + Duration::days(rng.gen_range(30..50) * 365);
         let hasta = desde + Duration::days(365 * rng.gen_range(1..5));
         Self {
@@ -348,29 +417,42 @@ impl AntecedentesProfesionales {
             desde,
             hasta,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla Publicaciones.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Publicaciones {
     pub id_publicacion: u32,
+    #[dbdata(varchar(200))]
     pub autores: String,
     pub anio: i32,
+    #[dbdata(varchar(50))]
     pub titulo: String,
 }
 
+/// Máximo de coautores externos, que no son profesores de la base de datos, de una publicación.
+const MAX_COAUTORES_EXTERNOS: usize = 2;
+
+/// Nombre de un autor como se cita en [`Publicaciones::autores`].
+fn nombre_autor(apellido: &str, nombre: &str) -> String {
+    format!("{apellido}, {nombre}")
+}
+
+fn autor_ficticio<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let nombre: String = FirstName().fake_with_rng(&mut *rng);
+    let apellido: String = LastName().fake_with_rng(&mut *rng);
+    nombre_autor(&apellido, &nombre)
+}
+
 impl Dummy<Faker> for Publicaciones {
     fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
         let id_publicacion = rng.gen();
         let anio = rng.gen_range(1901..2155);
-        let titulo: String = Word().fake();
+        let titulo: String = Word().fake_with_rng(&mut *rng);
         let autores = (1..rng.gen_range(2..5))
-            .map(|_| {
-                let nombre: String = FirstName().fake();
-                let apellido: String = LastName().fake();
-                format!("{apellido}, {nombre}")
-            })
+            .map(|_| autor_ficticio(rng))
             .collect::<Vec<String>>()
             .join("; ");
         Self {
@@ -379,35 +461,105 @@ impl Dummy<Faker> for Publicaciones {
             anio,
             titulo,
         }
+        .ajustada()
+    }
+}
+
+impl Publicaciones {
+    /// Largo máximo de la columna Autores.
+    fn largo_autores() -> usize {
+        match Self::tipo_columna("autores") {
+            Some(TipoColumna::Texto(largo)) => largo,
+            _ => usize::MAX,
+        }
+    }
+
+    /// Cantidad de los primeros `autores` cuyos nombres entran juntos en la columna Autores.
+    pub fn autores_que_entran(autores: &[&Profesores]) -> usize {
+        let maximo = Self::largo_autores();
+        let mut largo = 0;
+        autores
+            .iter()
+            .take_while(|p| {
+                if largo > 0 {
+                    largo += "; ".len();
+                }
+                largo += nombre_autor(&p.apellido, &p.nombre).chars().count();
+                largo <= maximo
+            })
+            .count()
+    }
+
+    /// Publicación de los profesores `autores`, que se registran en [`Publico`], y a veces de
+    /// coautores externos. Los autores que no entran en la columna Autores se omiten (ver
+    /// [`Self::autores_que_entran`]); sin profesores, todos los autores son inventados.
+    pub fn new(autores: &[&Profesores]) -> Self {
+        let mut rng = GLOBAL_RNG
+            .lock()
+            .expect("Fallo en adquirir el rng dentro del Mutex");
+        let mut publicacion: Self = Faker.fake_with_rng(&mut *rng);
+        let autores = &autores[..Self::autores_que_entran(autores)];
+        if autores.is_empty() {
+            return publicacion;
+        }
+        let mut nombres: Vec<String> = autores
+            .iter()
+            .map(|p| nombre_autor(&p.apellido, &p.nombre))
+            .collect();
+        let externos = if rng.gen_bool(0.3) {
+            rng.gen_range(1..=MAX_COAUTORES_EXTERNOS)
+        } else {
+            0
+        };
+        let maximo = Self::largo_autores();
+        let mut largo = nombres.join("; ").chars().count();
+        for _ in 0..externos {
+            let externo = autor_ficticio(&mut *rng);
+            largo += externo.chars().count() + "; ".len();
+            if largo > maximo {
+                break;
+            }
+            nombres.push(externo);
+        }
+        publicacion.autores = nombres.join("; ");
+        publicacion.ajustada()
     }
 }
 
 /// Representa a la tabla ReunionesCientificas.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct ReunionesCientificas {
+    #[dbdata(varchar(50))]
     pub titulo: String,
     pub fecha: Date,
 }
 
 impl Dummy<Faker> for ReunionesCientificas {
-    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, _: &mut R) -> Self {
-        let titulo = Words(1..5).fake::<Vec<String>>().join(" ");
+    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        let titulo = Words(1..5)
+            .fake_with_rng::<Vec<String>, _>(&mut *rng)
+            .join(" ");
         Self {
             titulo,
-            fecha: Date().fake(),
+            fecha: Date().fake_with_rng(&mut *rng),
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla DependenciasEmpresas.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct DependenciasEmpresas {
+    #[dbdata(char(8))]
     pub dni_profesor: Dni, //WARN: FK de Profesores
+    #[dbdata(varchar(100))]
     pub nombre: String,
-    pub tipo_actividad: String, // ('Autonomo', 'Dependencia')
+    pub tipo_actividad: String, // ('Autónomo', 'Dependencia')
+    #[dbdata(varchar(250))]
     pub observacion: String,
     pub naturaleza_juridica: String, // ('Privado', 'Publico')
     pub codigo_postal: u32,
+    #[dbdata(varchar(100))]
     pub calle: String,
     pub numero: u32,
     pub id_obrasocial: u32,
@@ -419,12 +571,14 @@ impl DependenciasEmpresas {
             .lock()
             .expect("Fallo en adquirir el rng dentro del Mutex");
         let dni_profesor = profesor.dni.clone();
-        let nombre = CompanyName().fake();
-        let tipo_actividad = ["Autonomo", "Dependencia"]
+        let nombre = CompanyName().fake_with_rng(&mut *rng);
+        let tipo_actividad = ["Autónomo", "Dependencia"]
             .choose(&mut *rng)
             .unwrap()
             .to_string();
-        let observacion = Words(1..20).fake::<Vec<String>>().join(" ");
+        let observacion = Words(1..20)
+            .fake_with_rng::<Vec<String>, _>(&mut *rng)
+            .join(" ");
         let naturaleza_juridica = ["Privado", "Publico"]
             .choose(&mut *rng)
             .unwrap()
@@ -441,13 +595,15 @@ impl DependenciasEmpresas {
             numero: direccion.numero,
             id_obrasocial: obra.id_obrasocial,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla ObrasSociales.
-#[derive(Debug, Dummy, DBData)]
+#[derive(Debug, Clone, Dummy, DBData)]
 pub struct ObrasSociales {
     #[dummy(faker = "CompanyName()")]
+    #[dbdata(varchar(100))]
     pub nombre_obra: String,
     #[dummy(faker = "..")]
     pub id_obrasocial: u32,
@@ -459,18 +615,21 @@ impl ObrasSociales {
             nombre_obra: nombre_obra.to_string(),
             id_obrasocial,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla DocObraSocial.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct DocObraSocial {
     pub id_doc: u32,
     pub id_obra_social: u32,
-    pub dni_profesor: Dni,     //WARN: FK de Profesores
+    #[dbdata(decimal(8, 0))]
+    pub dni_profesor: Dni, //WARN: FK de Profesores
     pub tipo_personal: String, // ('No Docente', 'Docente', 'Contratado', 'Becario')
     pub tipo_caracter: String, // ('Titular', 'Suplente', 'Graduado', 'Estudiante', 'Interino')
     pub presta_servicios: bool,
+    #[dbdata(varchar(100))]
     pub dependencia: String,
 }
 
@@ -491,7 +650,7 @@ impl DocObraSocial {
             .unwrap()
             .to_string();
         let presta_servicios = rng.gen::<bool>();
-        let dependencia: String = Word().fake();
+        let dependencia: String = Word().fake_with_rng(&mut *rng);
 
         Self {
             id_doc,
@@ -502,47 +661,66 @@ impl DocObraSocial {
             presta_servicios,
             dependencia,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla Percepciones.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Percepciones {
+    #[dbdata(varchar(100))]
     pub institucion_caja: String,
+    #[dbdata(varchar(50))]
     pub tipo: String,
+    #[dbdata(varchar(50))]
     pub regimen: String,
+    #[dbdata(varchar(50))]
     pub causa: String,
 }
 
-impl Dummy<Faker> for Percepciones {
-    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, _: &mut R) -> Self {
+impl Percepciones {
+    /// Percepción de `percepcion` en la caja previsional `caja`, o en una inventada si no se
+    /// indica.
+    pub fn new(percepcion: &TipoPercepcion, caja: Option<&str>) -> Self {
+        let mut rng = GLOBAL_RNG
+            .lock()
+            .expect("Fallo en adquirir el rng dentro del Mutex");
+        let institucion_caja = match caja {
+            Some(caja) => caja.to_string(),
+            None => Words(1..10)
+                .fake_with_rng::<Vec<String>, _>(&mut *rng)
+                .join(" "),
+        };
         Self {
-            institucion_caja: Words(1..10).fake::<Vec<String>>().join(" "),
-            tipo: Words(1..3).fake::<Vec<String>>().join(" "),
-            regimen: Word().fake(),
-            causa: Words(1..5).fake::<Vec<String>>().join(" "),
+            institucion_caja,
+            tipo: percepcion.tipo.clone(),
+            regimen: percepcion.regimen.clone(),
+            causa: percepcion.causa.clone(),
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla DeclaracionesJuradas.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct DeclaracionesJuradas {
     pub id_declaracion: u32,
+    #[dbdata(char(8))]
     pub dni_profesor: Dni, // WARN: FK de Profesores
     pub fecha: Date,
+    #[dbdata(varchar(100))]
     pub lugar: String,
 }
 
 impl DeclaracionesJuradas {
     pub fn new(profesor: &Profesores) -> Self {
-        let id_declaracion = GLOBAL_RNG
+        let mut rng = GLOBAL_RNG
             .lock()
-            .expect("Fallo en adquirir el rng dentro del Mutex")
-            .gen();
+            .expect("Fallo en adquirir el rng dentro del Mutex");
+        let id_declaracion = rng.gen();
         let dni_profesor = profesor.dni.clone();
-        let lugar = CityName().fake();
-        let fecha = Date().fake();
+        let lugar = CityName().fake_with_rng(&mut *rng);
+        let fecha = Date().fake_with_rng(&mut *rng);
 
         Self {
             id_declaracion,
@@ -550,16 +728,20 @@ impl DeclaracionesJuradas {
             fecha,
             lugar,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla Direcciones.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Direcciones {
     pub codigo_postal: u32,
+    #[dbdata(varchar(100))]
     pub calle: String,
     pub numero: u32,
+    #[dbdata(varchar(100))]
     pub localidad: String,
+    #[dbdata(varchar(100))]
     pub provincia: String,
 }
 
@@ -567,7 +749,7 @@ impl Direcciones {
     pub fn new(provincia: &str, localidad: &str, calle: &str) -> Self {
         let mut rng = GLOBAL_RNG.lock().unwrap();
         let numero = BuildingNumber()
-            .fake::<String>()
+            .fake_with_rng::<String, _>(&mut *rng)
             .parse()
             .expect("Fallo en transformar String a u32 'BuildingNumber()'");
 
@@ -580,31 +762,37 @@ impl Direcciones {
             localidad: localidad.to_string(),
             provincia: provincia.to_string(),
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla DeclaracionesDeCargo.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct DeclaracionesDeCargo {
+    #[dbdata(char(8))]
     pub dni_profesor: Dni,
+    #[dbdata(varchar(100))]
     pub nombre_dep: String,
     pub id_declaracion: u32,
+    #[dbdata(varchar(100))]
     pub cumple_horario: String,
+    #[dbdata(varchar(100))]
     pub reparticion: String,
+    #[dbdata(varchar(100))]
     pub dependencia: String,
 }
 
 impl DeclaracionesDeCargo {
     pub fn new(dep: &DependenciasEmpresas) -> Self {
-        let id_declaracion = GLOBAL_RNG
+        let mut rng = GLOBAL_RNG
             .lock()
-            .expect("Fallo en adquirir el rng dentro del Mutex")
-            .gen();
+            .expect("Fallo en adquirir el rng dentro del Mutex");
+        let id_declaracion = rng.gen();
         let dni_profesor = dep.dni_profesor.clone();
         let nombre_dep = dep.nombre.clone();
-        let cumple_horario = Word().fake();
-        let reparticion = Word().fake();
-        let dependencia = Word().fake();
+        let cumple_horario = Word().fake_with_rng(&mut *rng);
+        let reparticion = Word().fake_with_rng(&mut *rng);
+        let dependencia = Word().fake_with_rng(&mut *rng);
         Self {
             dni_profesor,
             nombre_dep,
@@ -613,21 +801,23 @@ impl DeclaracionesDeCargo {
             reparticion,
             dependencia,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla Horarios.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Horarios {
     pub id_declaracion: u32, //WARN: FK de DeclaracionesDeCargo
     pub dia: String,         // ('Lunes','Martes','Miercoles','Jueves','Viernes')
     pub hora_inicio: time::Time,
     pub hora_fin: time::Time,
+    #[dbdata(varchar(50))]
     pub nombre_catedra: String,
 }
 
 impl Horarios {
-    pub fn new(declaracion: &DeclaracionesDeCargo) -> Self {
+    pub fn new(declaracion: &DeclaracionesDeCargo, catedra: &str) -> Self {
         let mut rng = GLOBAL_RNG
             .lock()
             .expect("Fallo en adquirir el rng dentro del Mutex");
@@ -637,9 +827,13 @@ impl Horarios {
             .unwrap()
             .to_string();
 
-        let hora_inicio = Time().fake();
-        let hora_fin = hora_inicio + Duration::hours(rng.gen_range(1..=8));
-        let nombre_catedra = Words(1..5).fake::<Vec<String>>().join(" ");
+        // El horario termina el mismo día, a más tardar a las 23:59.
+        let duracion = Duration::hours(rng.gen_range(1..=8));
+        let ultimo_inicio = (Duration::minutes(23 * 60 + 59) - duracion).whole_minutes();
+        let hora_inicio =
+            time::Time::MIDNIGHT + Duration::minutes(rng.gen_range(0..=ultimo_inicio));
+        let hora_fin = hora_inicio + duracion;
+        let nombre_catedra = catedra.to_string();
         Self {
             id_declaracion,
             dia,
@@ -647,18 +841,22 @@ impl Horarios {
             hora_fin,
             nombre_catedra,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla Empleadores.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Empleadores {
+    #[dbdata(char(11))]
     pub cuit: Cuil,
+    #[dbdata(varchar(100))]
     pub razon_social: String,
     pub piso: Option<u32>,
     pub departamento: Option<u8>,
     //WARN: FK de Direcciones
     pub codigo_postal: u32,
+    #[dbdata(varchar(100))]
     pub calle: String,
     pub numero: u32,
 }
@@ -668,7 +866,7 @@ impl Empleadores {
         let mut rng = GLOBAL_RNG
             .lock()
             .expect("Fallo en adquirir el rng dentro del Mutex");
-        let razon_social = Name().fake();
+        let razon_social = Name().fake_with_rng(&mut *rng);
         let vive_en_departamento = rng.gen::<bool>();
         let piso = if vive_en_departamento {
             Some(rng.gen_range(1..1000))
@@ -681,7 +879,7 @@ impl Empleadores {
         } else {
             None
         };
-        let dni: Dni = Faker.fake();
+        let dni: Dni = Faker.fake_with_rng(&mut *rng);
         Self {
             cuit: Cuil::new(&dni),
             razon_social,
@@ -691,24 +889,42 @@ impl Empleadores {
             calle: direccion.calle.clone(),
             numero: direccion.numero,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla Seguros.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Seguros {
     pub codigo_compania: u32,
+    #[dbdata(varchar(100))]
     pub compania_aseguradora: String,
+    #[dbdata(varchar(100))]
     pub lugar_emision: String,
     pub fecha_emision: Date,
 }
 
+impl Seguros {
+    pub fn new(compania_aseguradora: &str) -> Self {
+        let mut rng = GLOBAL_RNG
+            .lock()
+            .expect("Fallo en adquirir el rng dentro del Mutex");
+        Self {
+            codigo_compania: rng.gen(),
+            compania_aseguradora: compania_aseguradora.to_string(),
+            lugar_emision: CityName().fake_with_rng(&mut *rng),
+            fecha_emision: Date().fake_with_rng(&mut *rng),
+        }
+        .ajustada()
+    }
+}
+
 impl Dummy<Faker> for Seguros {
     fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
         let codigo_compania = rng.gen();
-        let compania_aseguradora = CompanyName().fake();
-        let lugar_emision = CityName().fake();
-        let fecha_emision = Date().fake();
+        let compania_aseguradora = CompanyName().fake_with_rng(&mut *rng);
+        let lugar_emision = CityName().fake_with_rng(&mut *rng);
+        let fecha_emision = Date().fake_with_rng(&mut *rng);
 
         Self {
             codigo_compania,
@@ -716,19 +932,26 @@ impl Dummy<Faker> for Seguros {
             lugar_emision,
             fecha_emision,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla Familiares.
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Familiares {
+    #[dbdata(char(8))]
     pub dni_profesor: Dni,
+    #[dbdata(char(8))]
     pub dni_familiar: Dni,
+    #[dbdata(varchar(50))]
     pub nombre: String,
+    #[dbdata(varchar(50))]
     pub apellido: String,
     pub parentesco: String,
     pub fecha_nacimiento: Date,
+    #[dbdata(varchar(50))]
     pub tipo_documento: String,
+    #[dbdata(decimal(10, 0))]
     pub porcentaje: BigDecimal,
     pub piso: Option<u32>,
     pub departamento: Option<u8>,
@@ -736,6 +959,7 @@ pub struct Familiares {
     //WARN: FK de Direcciones
     pub numero_dir: u32,
     pub codigo_postal: u32,
+    #[dbdata(varchar(100))]
     pub calle: String,
 }
 
@@ -744,10 +968,10 @@ impl Familiares {
         let mut rng = GLOBAL_RNG
             .lock()
             .expect("Fallo en adquirir el rng dentro del Mutex");
-        let dni_familiar = Faker.fake();
+        let dni_familiar = Faker.fake_with_rng(&mut *rng);
         let dni_profesor = profesor.dni.clone();
-        let nombre = FirstName().fake();
-        let apellido = LastName().fake();
+        let nombre = FirstName().fake_with_rng(&mut *rng);
+        let apellido = LastName().fake_with_rng(&mut *rng);
         let parentesco = ["Cónyuge", "Hijo", "Padre", "Pareja", "Hermano"]
             .choose(&mut *rng)
             .unwrap()
@@ -758,11 +982,11 @@ impl Familiares {
                 .expect("Fallo en generar una fecha de nacimiento");
 
         //FIXME: Que tipos de documento pueden ser?
-        let tipo_documento = Word().fake();
+        let tipo_documento = Word().fake_with_rng(&mut *rng);
 
         // FIXME: Va a ocurrir que entre los beneficiarios o no cubriran el 100% o sobrepasaran el
         // 100%, por como está definido esto.
-        let porcentaje = BigDecimal::new(rng.gen_range(1..2).into(), rng.gen_range(1..27));
+        let porcentaje = BigDecimal::from(rng.gen_range(1..=100));
         let vive_en_departamento = rng.gen::<bool>();
         let piso = if vive_en_departamento {
             Some(rng.gen_range(1..1000))
@@ -794,15 +1018,19 @@ impl Familiares {
             codigo_postal,
             calle,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla AseguraA
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct AseguraA {
+    #[dbdata(char(8))]
     pub dni_profesor: Dni,
+    #[dbdata(char(8))]
     pub dni_familiar: Dni,
     pub codigo_compania: u32,
+    #[dbdata(decimal(10, 0))]
     pub capital_asegurado: f64,
     pub fecha_ingreso: Date,
 }
@@ -811,8 +1039,9 @@ impl AseguraA {
     pub fn new(seguro: &Seguros, familiar: &Familiares) -> Self {
         let dni_profesor = familiar.dni_profesor.clone();
         let dni_familiar = familiar.dni_familiar.clone();
-        let fecha_ingreso = Date().fake();
-        let capital_asegurado = GLOBAL_RNG.lock().unwrap().gen_range(100_000.0..1_000_000.0);
+        let mut rng = GLOBAL_RNG.lock().unwrap();
+        let fecha_ingreso = Date().fake_with_rng(&mut *rng);
+        let capital_asegurado = rng.gen_range(100_000.0..1_000_000.0);
         let codigo_compania = seguro.codigo_compania;
         Self {
             dni_profesor,
@@ -821,16 +1050,19 @@ impl AseguraA {
             capital_asegurado,
             fecha_ingreso,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla ResideEn
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct ResideEn {
+    #[dbdata(char(8))]
     pub dni_profesor: Dni,
     pub piso: Option<u32>,
     pub departamento: Option<u8>,
     pub codigo_postal: u32,
+    #[dbdata(varchar(100))]
     pub calle: String,
     pub numero: u32,
 }
@@ -858,14 +1090,18 @@ impl ResideEn {
             calle: direccion.calle.clone(),
             numero: direccion.numero,
         }
+        .ajustada()
     }
 }
 
 /// Representa a la tabla PercibeEn
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct PercibeEn {
+    #[dbdata(char(8))]
     pub dni: Dni,
+    #[dbdata(varchar(100))]
     pub institucion_caja: String,
+    #[dbdata(varchar(50))]
     pub tipo: String,
     pub estado_percepcion: String,
     pub desde: Date,
@@ -873,9 +1109,10 @@ pub struct PercibeEn {
 
 impl PercibeEn {
     pub fn new(percepcion: &Percepciones, profesor: &Profesores) -> Self {
-        let desde: Date = Date().fake();
+        let mut rng = GLOBAL_RNG.lock().unwrap();
+        let desde: Date = Date().fake_with_rng(&mut *rng);
         let estado_percepcion = ["Suspendido", "Percibiendo"]
-            .choose(&mut *GLOBAL_RNG.lock().unwrap())
+            .choose(&mut *rng)
             .unwrap()
             .to_string();
         Self {
@@ -885,32 +1122,39 @@ impl PercibeEn {
             estado_percepcion,
             desde,
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct ParticipoEnReunion {
+    #[dbdata(char(8))]
     dni_profesor: Dni,
+    #[dbdata(varchar(50))]
     titulo: String,
     fecha: Date,
+    #[dbdata(varchar(50))]
     participacion: String,
 }
 
 impl ParticipoEnReunion {
     pub fn new(reunion: &ReunionesCientificas, profesor: &Profesores) -> Self {
-        let participacion: String = Word().fake();
+        let mut rng = GLOBAL_RNG.lock().unwrap();
+        let participacion: String = Word().fake_with_rng(&mut *rng);
         Self {
             dni_profesor: profesor.dni.clone(),
             titulo: reunion.titulo.clone(),
             fecha: reunion.fecha,
             participacion,
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Publico {
     id_publicacion: u32,
+    #[dbdata(char(8))]
     dni_profesor: Dni,
 }
 
@@ -920,10 +1164,11 @@ impl Publico {
             id_publicacion: publicacion.id_publicacion,
             dni_profesor: profesor.dni.clone(),
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct ReferenciaBibliografica {
     id_fuente: u32,
     id_citador: u32,
@@ -934,13 +1179,16 @@ impl ReferenciaBibliografica {
             id_fuente: fuente.id_publicacion,
             id_citador: citador.id_publicacion,
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct RealizoAct {
     id_actividad: u32,
+    #[dbdata(char(8))]
     dni_profesor: Dni,
+    #[dbdata(varchar(50))]
     acciones: String,
     dedicacion: u32,
     hasta: Date,
@@ -950,9 +1198,9 @@ pub struct RealizoAct {
 impl RealizoAct {
     pub fn new(actividad: &ActividadesExtensionUniversitaria, profesor: &Profesores) -> Self {
         let mut rng = GLOBAL_RNG.lock().unwrap();
-        let acciones: String = Word().fake();
+        let acciones: String = Word().fake_with_rng(&mut *rng);
         let dedicacion = rng.gen_range(1..8);
-        let desde: Date = Date().fake();
+        let desde: Date = Date().fake_with_rng(&mut *rng);
         let hasta = desde + Duration::days(365);
         Self {
             id_actividad: actividad.id_actividad,
@@ -962,12 +1210,14 @@ impl RealizoAct {
             hasta,
             desde,
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct RealizaInves {
     id_investigacion: u32,
+    #[dbdata(char(8))]
     dni_profesor: Dni,
     dedicacion: u32,
     desde: Date,
@@ -978,7 +1228,7 @@ impl RealizaInves {
     pub fn new(actividad: &ActividadesInvestigacion, profesor: &Profesores) -> Self {
         let mut rng = GLOBAL_RNG.lock().unwrap();
         let dedicacion = rng.gen_range(1..8);
-        let desde: Date = Date().fake();
+        let desde: Date = Date().fake_with_rng(&mut *rng);
         let hasta = if rng.gen::<bool>() {
             Some(desde + Duration::days(365))
         } else {
@@ -991,13 +1241,17 @@ impl RealizaInves {
             hasta,
             desde,
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct SeDaTitulo {
+    #[dbdata(varchar(100))]
     titulo: String,
+    #[dbdata(varchar(255))]
     nombre_inst: String,
+    #[dbdata(varchar(50))]
     nivel: String,
 }
 
@@ -1008,13 +1262,17 @@ impl SeDaTitulo {
             nombre_inst: inst.nombre.clone(),
             nivel: titulo.nivel.clone(),
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct PoseeTitulo {
+    #[dbdata(char(8))]
     dni: Dni,
+    #[dbdata(varchar(50))]
     nivel: String,
+    #[dbdata(varchar(100))]
     titulo: String,
     desde: Date,
     hasta: Date,
@@ -1022,7 +1280,8 @@ pub struct PoseeTitulo {
 
 impl PoseeTitulo {
     pub fn new(titulo: &Titulos, profesor: &Profesores) -> Self {
-        let desde: Date = Date().fake();
+        let mut rng = GLOBAL_RNG.lock().unwrap();
+        let desde: Date = Date().fake_with_rng(&mut *rng);
         let hasta = desde + Duration::days(365 * 5);
         Self {
             dni: profesor.dni.clone(),
@@ -1031,12 +1290,15 @@ impl PoseeTitulo {
             desde,
             hasta,
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Beneficia {
+    #[dbdata(char(8))]
     dni_familiar: Dni,
+    #[dbdata(char(8))]
     dni_profesor: Dni,
     id_obrasocial: u32,
 }
@@ -1048,12 +1310,15 @@ impl Beneficia {
             dni_profesor: familiar.dni_profesor.clone(),
             id_obrasocial: obra.id_obrasocial,
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct SeDaIdioma {
+    #[dbdata(varchar(50))]
     nombre_idioma: String,
+    #[dbdata(varchar(255))]
     nombre_inst: String,
 }
 
@@ -1063,12 +1328,15 @@ impl SeDaIdioma {
             nombre_idioma: idioma.nombre.clone(),
             nombre_inst: inst.nombre.clone(),
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct AtendioA {
+    #[dbdata(varchar(100))]
     nombre_curso: String,
+    #[dbdata(char(8))]
     dni_profesor: Dni,
     desde: Date,
     hasta: Option<Date>,
@@ -1076,7 +1344,8 @@ pub struct AtendioA {
 
 impl AtendioA {
     pub fn new(curso: &CursosConferencias, prof: &Profesores) -> Self {
-        let desde: Date = Date().fake();
+        let mut rng = GLOBAL_RNG.lock().unwrap();
+        let desde: Date = Date().fake_with_rng(&mut *rng);
         let hasta = match curso.tipo.as_str() {
             "Curso" => Some(desde + Duration::days(30)),
             "Conferencia" => Some(desde + Duration::days(1)),
@@ -1088,12 +1357,14 @@ impl AtendioA {
             desde,
             hasta,
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct Idiomas {
-    nombre: String,
+    #[dbdata(varchar(50))]
+    pub nombre: String,
 }
 
 impl Idiomas {
@@ -1101,27 +1372,59 @@ impl Idiomas {
         Self {
             nombre: nombre.to_string(),
         }
+        .ajustada()
     }
 }
 
-#[derive(Debug, DBData)]
+#[derive(Debug, Clone, DBData)]
 pub struct ConoceIdioma {
+    #[dbdata(char(8))]
     dni_profesor: Dni,
+    #[dbdata(varchar(50))]
     nombre_idioma: String,
+    #[dbdata(varchar(50))]
     certificacion: String,
+    #[dbdata(varchar(50))]
     nivel: String,
 }
 
+/// Niveles del Marco Común Europeo de Referencia para las Lenguas.
+const NIVELES_MCER: [&str; 6] = ["A1", "A2", "B1", "B2", "C1", "C2"];
+
 impl ConoceIdioma {
-    pub fn new(idioma: &Idiomas, profesor: &Profesores) -> Self {
-        let nivel: String = Word().fake();
-        let certificacion: String = Word().fake();
+    /// Conocimiento de `idioma` acreditado con `certificacion`, que debe ser de ese idioma. Sin
+    /// certificación el nivel se elige al azar.
+    pub fn new(
+        idioma: &Idiomas,
+        profesor: &Profesores,
+        certificacion: Option<&Certificacion>,
+    ) -> Self {
+        let (certificacion, nivel) = match certificacion {
+            Some(c) => (c.nombre.clone(), c.nivel.clone()),
+            None => {
+                let mut rng = GLOBAL_RNG.lock().unwrap();
+                let nivel = NIVELES_MCER.choose(&mut *rng).unwrap();
+                ("Sin certificación".to_string(), nivel.to_string())
+            }
+        };
         Self {
             dni_profesor: profesor.dni.clone(),
             nombre_idioma: idioma.nombre.clone(),
             certificacion,
             nivel,
         }
+        .ajustada()
+    }
+
+    /// El español, que todos los profesores tienen como lengua materna.
+    pub fn nativo(profesor: &Profesores) -> Self {
+        Self {
+            dni_profesor: profesor.dni.clone(),
+            nombre_idioma: "Español".to_string(),
+            certificacion: "Lengua materna".to_string(),
+            nivel: "C2".to_string(),
+        }
+        .ajustada()
     }
 }
 
@@ -1136,6 +1439,24 @@ impl Cuil {
     }
 }
 
+impl ValorTsv for Cuil {
+    fn escribir_tsv(&self, salida: &mut String) {
+        self.0.escribir_tsv(salida);
+    }
+}
+
+impl ValorColumna for Cuil {
+    fn valor(&self) -> Valor {
+        self.0.valor()
+    }
+}
+
+impl Ajustable for Cuil {
+    fn ajustar(&mut self, tipo: TipoColumna) {
+        self.0.ajustar(tipo);
+    }
+}
+
 #[derive(Debug, Clone, Type)]
 #[sqlx(transparent)]
 pub struct Dni(String);
@@ -1146,3 +1467,21 @@ impl Dummy<Faker> for Dni {
         Self(dni)
     }
 }
+
+impl ValorTsv for Dni {
+    fn escribir_tsv(&self, salida: &mut String) {
+        self.0.escribir_tsv(salida);
+    }
+}
+
+impl ValorColumna for Dni {
+    fn valor(&self) -> Valor {
+        self.0.valor()
+    }
+}
+
+impl Ajustable for Dni {
+    fn ajustar(&mut self, tipo: TipoColumna) {
+        self.0.ajustar(tipo);
+    }
+}
@@ -0,0 +1,373 @@
+// Autor: Acosta Quintana, Lautaro
+//! Destinos de las filas generadas: una base de datos, un script SQL, un directorio de archivos
+//! CSV o la memoria. La carga envía cada fila a un [`Destino`], por lo que la misma carga se puede
+//! enviar a varios a la vez con [`Destinos`], como una base de datos y un respaldo en SQL.
+
+use std::{
+    any::Any,
+    collections::{hash_map::Entry, HashMap},
+    fmt::Write as _,
+    fs::{self, File},
+    future::Future,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use dbdata::{Conexion, Dialecto, Insertable, TipoColumna, Valor};
+use sqlx::{MySql, Postgres, Sqlite};
+
+use crate::{conexion::Motor, contar_filas, insertar, masiva::volcar_pendientes};
+
+/// Resultado de una operación asincrónica de un [`Destino`].
+pub type Futuro<'a, T> = Pin<Box<dyn Future<Output = anyhow::Result<T>> + 'a>>;
+
+/// Fila generada de cualquiera de las tablas.
+pub trait FilaGenerada: Send + Sync {
+    fn tabla(&self) -> &'static str;
+    fn columnas(&self) -> &'static [&'static str];
+    /// Tipos de las columnas, en el orden de [`FilaGenerada::columnas`].
+    fn tipos(&self) -> &'static [TipoColumna];
+    /// Valores de las columnas, en el orden de [`FilaGenerada::columnas`].
+    fn valores(&self) -> Vec<Valor>;
+    /// Inserta la fila en `conexion` con [`crate::insertar`].
+    fn insertar_en<'a>(&'a self, conexion: &'a Conexion) -> Futuro<'a, bool>;
+    fn como_any(&self) -> &dyn Any;
+    fn clonar(&self) -> Box<dyn FilaGenerada>;
+}
+
+impl<T> FilaGenerada for T
+where
+    T: Insertable + Clone + Send + Sync + 'static,
+{
+    fn tabla(&self) -> &'static str {
+        T::TABLA
+    }
+
+    fn columnas(&self) -> &'static [&'static str] {
+        T::COLUMNAS
+    }
+
+    fn tipos(&self) -> &'static [TipoColumna] {
+        T::TIPOS
+    }
+
+    fn valores(&self) -> Vec<Valor> {
+        dbdata::Fila::valores(self)
+    }
+
+    fn insertar_en<'a>(&'a self, conexion: &'a Conexion) -> Futuro<'a, bool> {
+        Box::pin(insertar(conexion, self))
+    }
+
+    fn como_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clonar(&self) -> Box<dyn FilaGenerada> {
+        Box::new(self.clone())
+    }
+}
+
+/// Lugar al que se envían las filas generadas.
+pub trait Destino: Send + Sync {
+    /// Envía `fila` y devuelve si el destino la aceptó. Las filas que el destino rechaza, como las
+    /// que no cumplen una restricción de la base de datos, se informan sin devolver un error.
+    fn enviar<'a>(&'a self, fila: &'a dyn FilaGenerada) -> Futuro<'a, bool>;
+
+    /// Indica si `tabla` ya tiene filas, en cuyo caso la carga no le envía las filas que salen de
+    /// los datasets.
+    fn tiene_filas<'a>(&'a self, _tabla: &'a str) -> Futuro<'a, bool> {
+        Box::pin(async { Ok(false) })
+    }
+
+    /// Termina de enviar las filas que el destino haya acumulado.
+    fn terminar(&self) -> Futuro<'_, ()> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Inserta las filas en la base de datos, con la carga masiva si está activada. Las filas
+/// cargadas, rechazadas y reintentadas se cuentan en [`crate::CONTADOR`]. Con la carga masiva
+/// todas las filas se consideran aceptadas, porque se envían después.
+impl Destino for Conexion {
+    fn enviar<'a>(&'a self, fila: &'a dyn FilaGenerada) -> Futuro<'a, bool> {
+        fila.insertar_en(self)
+    }
+
+    fn tiene_filas<'a>(&'a self, tabla: &'a str) -> Futuro<'a, bool> {
+        Box::pin(async move { Ok(contar_filas(self, tabla).await? > 0) })
+    }
+
+    fn terminar(&self) -> Futuro<'_, ()> {
+        Box::pin(volcar_pendientes(self))
+    }
+}
+
+/// Envía cada fila a todos los destinos, en orden. Si uno la rechaza o falla, la fila no se envía
+/// a los siguientes, por lo que un respaldo después de la base de datos sólo tiene las filas que
+/// la base de datos aceptó.
+#[derive(Default, Clone)]
+pub struct Destinos(pub Vec<Arc<dyn Destino>>);
+
+impl Destino for Destinos {
+    fn enviar<'a>(&'a self, fila: &'a dyn FilaGenerada) -> Futuro<'a, bool> {
+        Box::pin(async move {
+            for destino in &self.0 {
+                if !destino.enviar(fila).await? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        })
+    }
+
+    /// Una tabla tiene filas si las tiene en alguno de los destinos.
+    fn tiene_filas<'a>(&'a self, tabla: &'a str) -> Futuro<'a, bool> {
+        Box::pin(async move {
+            for destino in &self.0 {
+                if destino.tiene_filas(tabla).await? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        })
+    }
+
+    fn terminar(&self) -> Futuro<'_, ()> {
+        Box::pin(async move {
+            for destino in &self.0 {
+                destino.terminar().await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Escribe las filas como un script SQL de un INSERT por fila para el motor indicado. Los INSERT
+/// descartan las filas que no se pueden insertar, como hace la base de datos durante la carga.
+pub struct ScriptSql {
+    motor: Motor,
+    archivo: Mutex<BufWriter<File>>,
+}
+
+impl ScriptSql {
+    /// Crea el archivo del script, reemplazándolo si ya existe.
+    pub fn crear(ruta: impl AsRef<Path>, motor: Motor) -> anyhow::Result<Self> {
+        let ruta = ruta.as_ref();
+        let archivo = File::create(ruta)
+            .map_err(|e| anyhow::anyhow!("No se pudo crear '{}': {e}", ruta.display()))?;
+        Ok(Self {
+            motor,
+            archivo: Mutex::new(BufWriter::new(archivo)),
+        })
+    }
+
+    fn insert(&self, fila: &dyn FilaGenerada) -> String {
+        let (inicio, fin) = match self.motor {
+            Motor::MySql => MySql::INSERTAR_IGNORANDO,
+            Motor::Postgres => Postgres::INSERTAR_IGNORANDO,
+            Motor::Sqlite => Sqlite::INSERTAR_IGNORANDO,
+        };
+        let mut sql = format!(
+            "{inicio} {} ({}) VALUES (",
+            fila.tabla(),
+            fila.columnas().join(",")
+        );
+        for (i, valor) in fila.valores().iter().enumerate() {
+            if i > 0 {
+                sql.push(',');
+            }
+            self.escribir_valor(valor, &mut sql);
+        }
+        sql.push(')');
+        sql.push_str(fin);
+        sql.push_str(";\n");
+        sql
+    }
+
+    fn escribir_valor(&self, valor: &Valor, sql: &mut String) {
+        match valor {
+            Valor::Nulo => sql.push_str("NULL"),
+            Valor::Booleano(b) => sql.push_str(match (self.motor, b) {
+                (Motor::Sqlite, true) => "1",
+                (Motor::Sqlite, false) => "0",
+                (_, true) => "TRUE",
+                (_, false) => "FALSE",
+            }),
+            Valor::Entero(n) => {
+                let _ = write!(sql, "{n}");
+            }
+            Valor::Real(n) => {
+                let _ = write!(sql, "{n}");
+            }
+            // SQLite guarda los decimales como texto.
+            Valor::Decimal(n) if self.motor == Motor::Sqlite => {
+                let _ = write!(sql, "'{n}'");
+            }
+            Valor::Decimal(n) => {
+                let _ = write!(sql, "{n}");
+            }
+            Valor::Texto(texto) => {
+                sql.push('\'');
+                for c in texto.chars() {
+                    match c {
+                        '\'' => sql.push_str("''"),
+                        // MySQL interpreta las barras invertidas como escapes.
+                        '\\' if self.motor == Motor::MySql => sql.push_str("\\\\"),
+                        c => sql.push(c),
+                    }
+                }
+                sql.push('\'');
+            }
+            // Postgres no acepta el año 0 como texto; el año 1 a.C. es el 0 de las fechas de Rust.
+            Valor::Fecha(fecha) if self.motor == Motor::Postgres && fecha.year() <= 0 => {
+                let _ = write!(
+                    sql,
+                    "'{:04}-{:02}-{:02} BC'",
+                    1 - fecha.year(),
+                    u8::from(fecha.month()),
+                    fecha.day()
+                );
+            }
+            Valor::Fecha(_) | Valor::Hora(_) => {
+                sql.push('\'');
+                sql.push_str(&texto_plano(valor));
+                sql.push('\'');
+            }
+        }
+    }
+}
+
+impl Destino for ScriptSql {
+    fn enviar<'a>(&'a self, fila: &'a dyn FilaGenerada) -> Futuro<'a, bool> {
+        let sql = self.insert(fila);
+        Box::pin(async move {
+            self.archivo.lock().unwrap().write_all(sql.as_bytes())?;
+            Ok(true)
+        })
+    }
+
+    fn terminar(&self) -> Futuro<'_, ()> {
+        Box::pin(async move {
+            self.archivo.lock().unwrap().flush()?;
+            Ok(())
+        })
+    }
+}
+
+/// Escribe las filas de cada tabla en un archivo CSV `<Tabla>.csv` de un directorio, con los
+/// nombres de las columnas en la primera línea. Los valores nulos se escriben vacíos y los
+/// booleanos como 1 o 0.
+pub struct DirectorioCsv {
+    directorio: PathBuf,
+    archivos: Mutex<HashMap<&'static str, csv::Writer<File>>>,
+}
+
+impl DirectorioCsv {
+    /// Crea el directorio si no existe. Los archivos de las tablas se reemplazan al recibir su
+    /// primera fila.
+    pub fn crear(directorio: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let directorio = directorio.into();
+        fs::create_dir_all(&directorio)
+            .map_err(|e| anyhow::anyhow!("No se pudo crear '{}': {e}", directorio.display()))?;
+        Ok(Self {
+            directorio,
+            archivos: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+impl Destino for DirectorioCsv {
+    fn enviar<'a>(&'a self, fila: &'a dyn FilaGenerada) -> Futuro<'a, bool> {
+        Box::pin(async move {
+            let mut archivos = self.archivos.lock().unwrap();
+            let escritor = match archivos.entry(fila.tabla()) {
+                Entry::Occupied(e) => e.into_mut(),
+                Entry::Vacant(e) => {
+                    let ruta = self.directorio.join(format!("{}.csv", fila.tabla()));
+                    let mut escritor = csv::Writer::from_path(&ruta).map_err(|err| {
+                        anyhow::anyhow!("No se pudo crear '{}': {err}", ruta.display())
+                    })?;
+                    escritor.write_record(fila.columnas())?;
+                    e.insert(escritor)
+                }
+            };
+            escritor.write_record(fila.valores().iter().map(texto_plano))?;
+            Ok(true)
+        })
+    }
+
+    fn terminar(&self) -> Futuro<'_, ()> {
+        Box::pin(async move {
+            for escritor in self.archivos.lock().unwrap().values_mut() {
+                escritor.flush()?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Valor sin comillas ni escapes, como se escribe en un CSV.
+fn texto_plano(valor: &Valor) -> String {
+    match valor {
+        Valor::Nulo => String::new(),
+        Valor::Booleano(b) => u8::from(*b).to_string(),
+        Valor::Entero(n) => n.to_string(),
+        Valor::Real(n) => n.to_string(),
+        Valor::Decimal(n) => n.to_string(),
+        Valor::Texto(texto) => texto.clone(),
+        Valor::Fecha(fecha) => format!(
+            "{:04}-{:02}-{:02}",
+            fecha.year(),
+            u8::from(fecha.month()),
+            fecha.day()
+        ),
+        Valor::Hora(hora) => format!(
+            "{:02}:{:02}:{:02}",
+            hora.hour(),
+            hora.minute(),
+            hora.second()
+        ),
+    }
+}
+
+/// Guarda en memoria una copia de las filas, en el orden en que se enviaron.
+#[derive(Default)]
+pub struct Memoria {
+    filas: Mutex<Vec<Box<dyn FilaGenerada>>>,
+}
+
+impl Memoria {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Filas recibidas de la tabla del tipo `T`.
+    pub fn filas<T: Clone + 'static>(&self) -> Vec<T> {
+        self.filas
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|f| f.como_any().downcast_ref::<T>().cloned())
+            .collect()
+    }
+
+    /// Cantidad de filas recibidas de `tabla`.
+    pub fn cantidad(&self, tabla: &str) -> usize {
+        self.filas
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|f| f.tabla() == tabla)
+            .count()
+    }
+}
+
+impl Destino for Memoria {
+    fn enviar<'a>(&'a self, fila: &'a dyn FilaGenerada) -> Futuro<'a, bool> {
+        self.filas.lock().unwrap().push(fila.clonar());
+        Box::pin(async { Ok(true) })
+    }
+}
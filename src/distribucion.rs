@@ -0,0 +1,220 @@
+// Autor: Acosta Quintana, Lautaro
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use rand::{seq::SliceRandom, Rng};
+use rand_distr::{Distribution, Normal, Zipf};
+
+/// Describe una relación 1:N entre una tabla padre y una tabla hija.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Relacion {
+    pub padre: &'static str,
+    /// Columna de la tabla padre referenciada por la hija.
+    pub clave_padre: &'static str,
+    pub hijo: &'static str,
+    /// Columna de la tabla hija que referencia al padre.
+    pub clave_hijo: &'static str,
+}
+
+const fn de_profesor(hijo: &'static str) -> Relacion {
+    Relacion {
+        padre: "Profesores",
+        clave_padre: "DNI",
+        hijo,
+        clave_hijo: "DNIProfesor",
+    }
+}
+
+/// Relaciones 1:N para las cuales se puede configurar cómo se reparten los hijos entre los padres.
+pub const RELACIONES: [Relacion; 8] = [
+    de_profesor("Contactos"),
+    de_profesor("DependenciasEmpresas"),
+    de_profesor("Familiares"),
+    de_profesor("DocObraSocial"),
+    de_profesor("DeclaracionesJuradas"),
+    de_profesor("AtendioA"),
+    de_profesor("Publico"),
+    de_profesor("ResideEn"),
+];
+
+/// Busca la relación cuya tabla hija es `hijo`.
+pub fn buscar_relacion(hijo: &str) -> Result<Relacion, String> {
+    RELACIONES
+        .iter()
+        .find(|r| r.hijo == hijo)
+        .copied()
+        .ok_or_else(|| {
+            format!(
+                "La relación '{hijo}' no es configurable. Las relaciones disponibles son: {}.",
+                RELACIONES.map(|r| r.hijo).join(", ")
+            )
+        })
+}
+
+/// Modela cómo se reparten las filas hijas de una relación entre las filas padre.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribucion {
+    /// Cada hijo elige a su padre con la misma probabilidad.
+    Uniforme,
+    /// Cada hijo elige a su padre según una ley de Zipf con el exponente indicado, por lo que
+    /// unos pocos padres concentran la mayoría de los hijos.
+    Zipf { exponente: f64 },
+    /// Cada padre tiene una cantidad de hijos que sigue una distribución normal.
+    Normal { media: f64, desvio: f64 },
+    /// Cada padre tiene entre `min` y `max` hijos, inclusive.
+    Fija { min: usize, max: usize },
+}
+
+impl Distribucion {
+    /// Indica si la cantidad de hijos la determina la distribución a partir de los padres en
+    /// lugar de la cantidad de muestras pedida.
+    pub fn es_por_padre(&self) -> bool {
+        matches!(
+            self,
+            Distribucion::Normal { .. } | Distribucion::Fija { .. }
+        )
+    }
+
+    /// Devuelve, para cada fila hija a generar, el índice del padre al que pertenece.
+    ///
+    /// Cuando la distribución es por hijo se generan `hijos` filas; cuando es por padre la
+    /// cantidad resulta de sumar los hijos de cada uno de los `padres`.
+    pub fn asignar<R: Rng + ?Sized>(&self, padres: usize, hijos: usize, rng: &mut R) -> Vec<usize> {
+        if padres == 0 {
+            return Vec::new();
+        }
+
+        match *self {
+            Distribucion::Uniforme => (0..hijos).map(|_| rng.gen_range(0..padres)).collect(),
+            Distribucion::Zipf { exponente } => {
+                // El rango de cada padre se asigna al azar para que los más populares no sean
+                // siempre los primeros en cargarse.
+                let mut rangos: Vec<usize> = (0..padres).collect();
+                rangos.shuffle(rng);
+                let zipf = Zipf::new(padres as u64, exponente)
+                    .expect("El exponente de Zipf fue validado al leerlo");
+                (0..hijos)
+                    .map(|_| rangos[zipf.sample(rng) as usize - 1])
+                    .collect()
+            }
+            Distribucion::Normal { media, desvio } => {
+                let normal = Normal::new(media, desvio).expect("El desvío fue validado al leerlo");
+                let mut asignacion = Vec::new();
+                for p in 0..padres {
+                    let cantidad = normal.sample(rng).round().max(0.0) as usize;
+                    asignacion.extend((0..cantidad).map(|_| p));
+                }
+                asignacion
+            }
+            Distribucion::Fija { min, max } => {
+                let mut asignacion = Vec::new();
+                for p in 0..padres {
+                    let cantidad = rng.gen_range(min..=max);
+                    asignacion.extend((0..cantidad).map(|_| p));
+                }
+                asignacion
+            }
+        }
+    }
+}
+
+impl fmt::Display for Distribucion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Distribucion::Uniforme => write!(f, "uniforme"),
+            Distribucion::Zipf { exponente } => write!(f, "zipf:{exponente}"),
+            Distribucion::Normal { media, desvio } => write!(f, "normal:{media},{desvio}"),
+            Distribucion::Fija { min, max } => write!(f, "fija:{min}..{max}"),
+        }
+    }
+}
+
+impl FromStr for Distribucion {
+    type Err = String;
+
+    /// Interpreta `uniforme`, `zipf:<exponente>`, `normal:<media>,<desvío>` o `fija:<min>..<max>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (nombre, parametros) = s.split_once(':').unwrap_or((s, ""));
+        // `NaN` e `inf` se interpretan como números, pero ninguna distribución los admite.
+        let numero = |v: &str| match v.trim().parse::<f64>() {
+            Ok(n) if n.is_finite() => Ok(n),
+            _ => Err(format!("'{v}' no es un número válido en '{s}'.")),
+        };
+
+        match nombre.trim().to_lowercase().as_str() {
+            "uniforme" => Ok(Distribucion::Uniforme),
+            "zipf" => {
+                let exponente = numero(parametros)?;
+                if exponente <= 0.0 {
+                    return Err(format!("El exponente de '{s}' debe ser mayor a 0."));
+                }
+                Ok(Distribucion::Zipf { exponente })
+            }
+            "normal" => {
+                let (media, desvio) = parametros
+                    .split_once(',')
+                    .ok_or_else(|| format!("Se esperaba 'normal:<media>,<desvío>' en '{s}'."))?;
+                let (media, desvio) = (numero(media)?, numero(desvio)?);
+                if media < 0.0 || desvio < 0.0 {
+                    return Err(format!("La media y el desvío de '{s}' no pueden ser negativos."));
+                }
+                Ok(Distribucion::Normal { media, desvio })
+            }
+            "fija" => {
+                let (min, max) = parametros
+                    .split_once("..")
+                    .ok_or_else(|| format!("Se esperaba 'fija:<min>..<max>' en '{s}'."))?;
+                let entero = |v: &str| {
+                    v.trim()
+                        .parse::<usize>()
+                        .map_err(|_| format!("'{v}' no es un entero válido en '{s}'."))
+                };
+                let (min, max) = (entero(min)?, entero(max)?);
+                if min > max {
+                    return Err(format!("El mínimo de '{s}' es mayor al máximo."));
+                }
+                Ok(Distribucion::Fija { min, max })
+            }
+            _ => Err(format!(
+                "Distribución desconocida '{nombre}'. Use uniforme, zipf:<exponente>, normal:<media>,<desvío> o fija:<min>..<max>."
+            )),
+        }
+    }
+}
+
+/// Distribución elegida para cada relación.
+#[derive(Debug, Clone, Default)]
+pub struct Distribuciones(HashMap<String, Distribucion>);
+
+impl Distribuciones {
+    /// Devuelve la distribución configurada para `relacion`, o `defecto` si no se configuró
+    /// ninguna.
+    pub fn de(&self, relacion: &str, defecto: Distribucion) -> Distribucion {
+        self.0.get(relacion).copied().unwrap_or(defecto)
+    }
+
+    pub fn configurar(&mut self, relacion: &str, distribucion: Distribucion) {
+        self.0.insert(relacion.to_string(), distribucion);
+    }
+
+    /// Reparte los hijos de `relacion` entre `padres` usando la distribución configurada, o
+    /// `defecto` si no se configuró ninguna. Ver [`Distribucion::asignar`].
+    pub fn asignar<R: Rng + ?Sized>(
+        &self,
+        relacion: &str,
+        defecto: Distribucion,
+        padres: usize,
+        hijos: usize,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        self.de(relacion, defecto).asignar(padres, hijos, rng)
+    }
+}
+
+/// Interpreta una asignación de la forma `<Relacion>=<distribución>`.
+pub fn parsear_asignacion(s: &str) -> Result<(String, Distribucion), String> {
+    let (relacion, distribucion) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Se esperaba '<Relacion>=<distribución>' en '{s}'."))?;
+    let relacion = buscar_relacion(relacion.trim())?;
+    Ok((relacion.hijo.to_string(), distribucion.parse()?))
+}
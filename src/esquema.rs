@@ -0,0 +1,404 @@
+// Autor: Acosta Quintana, Lautaro
+//! Descripción de las tablas de la migración que necesitan los comandos que revisan una base de
+//! datos ya cargada.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+/// Las 37 tablas del esquema, en el orden en que se cargan.
+pub const TABLAS: [&str; 37] = [
+    "Direcciones",
+    "Titulos",
+    "ReunionesCientificas",
+    "Percepciones",
+    "Seguros",
+    "ObrasSociales",
+    "Idiomas",
+    "Empleadores",
+    "Instituciones",
+    "CursosConferencias",
+    "ActividadesExtensionUniversitaria",
+    "ActividadesInvestigacion",
+    "Profesores",
+    "Publicaciones",
+    "Contactos",
+    "DependenciasEmpresas",
+    "Familiares",
+    "DocObraSocial",
+    "DeclaracionesJuradas",
+    "DeclaracionesDeCargo",
+    "AntecedentesProfesionales",
+    "AntecedentesDocentes",
+    "Horarios",
+    "AtendioA",
+    "ConoceIdioma",
+    "SeDaIdioma",
+    "Beneficia",
+    "PoseeTitulo",
+    "SeDaTitulo",
+    "RealizaInves",
+    "RealizoAct",
+    "ReferenciaBibliografica",
+    "Publico",
+    "ParticipoEnReunion",
+    "PercibeEn",
+    "ResideEn",
+    "AseguraA",
+];
+
+/// Una referencia de una tabla hacia otra, esté o no declarada como FOREIGN KEY en la migración.
+#[derive(Debug, Clone, Copy)]
+pub struct Referencia {
+    pub tabla: &'static str,
+    pub columnas: &'static [&'static str],
+    pub referida: &'static str,
+    pub columnas_referidas: &'static [&'static str],
+    /// `false` si la migración no declara la FOREIGN KEY y por lo tanto la base de datos no la
+    /// controla.
+    pub declarada: bool,
+}
+
+const fn fk(
+    tabla: &'static str,
+    columnas: &'static [&'static str],
+    referida: &'static str,
+    columnas_referidas: &'static [&'static str],
+) -> Referencia {
+    Referencia {
+        tabla,
+        columnas,
+        referida,
+        columnas_referidas,
+        declarada: true,
+    }
+}
+
+const DIRECCION: &[&str] = &["CodigoPostal", "Calle", "Numero"];
+
+/// Todas las referencias entre tablas del esquema.
+pub const REFERENCIAS: &[Referencia] = &[
+    fk("Instituciones", DIRECCION, "Direcciones", DIRECCION),
+    fk("Contactos", &["DNIProfesor"], "Profesores", &["DNI"]),
+    fk("SeDaIdioma", &["NombreIdioma"], "Idiomas", &["Nombre"]),
+    fk("SeDaIdioma", &["NombreInst"], "Instituciones", &["Nombre"]),
+    fk("ConoceIdioma", &["DNIProfesor"], "Profesores", &["DNI"]),
+    fk("ConoceIdioma", &["NombreIdioma"], "Idiomas", &["Nombre"]),
+    fk(
+        "SeDaTitulo",
+        &["Nivel", "Titulo"],
+        "Titulos",
+        &["Nivel", "Titulo"],
+    ),
+    fk("SeDaTitulo", &["NombreInst"], "Instituciones", &["Nombre"]),
+    fk(
+        "PoseeTitulo",
+        &["Nivel", "Titulo"],
+        "Titulos",
+        &["Nivel", "Titulo"],
+    ),
+    fk("PoseeTitulo", &["DNI"], "Profesores", &["DNI"]),
+    fk(
+        "CursosConferencias",
+        &["NombreInst"],
+        "Instituciones",
+        &["Nombre"],
+    ),
+    fk(
+        "AtendioA",
+        &["NombreCurso"],
+        "CursosConferencias",
+        &["NombreCurso"],
+    ),
+    fk("AtendioA", &["DNIProfesor"], "Profesores", &["DNI"]),
+    fk(
+        "ActividadesInvestigacion",
+        &["NombreInst"],
+        "Instituciones",
+        &["Nombre"],
+    ),
+    fk("RealizaInves", &["DNIProfesor"], "Profesores", &["DNI"]),
+    fk(
+        "RealizaInves",
+        &["IDInvestigacion"],
+        "ActividadesInvestigacion",
+        &["IDInvestigacion"],
+    ),
+    fk(
+        "ActividadesExtensionUniversitaria",
+        &["NombreInst"],
+        "Instituciones",
+        &["Nombre"],
+    ),
+    fk("RealizoAct", &["DNIProfesor"], "Profesores", &["DNI"]),
+    fk(
+        "RealizoAct",
+        &["IDActividad"],
+        "ActividadesExtensionUniversitaria",
+        &["IDActividad"],
+    ),
+    fk(
+        "DependenciasEmpresas",
+        &["IDObraSocial"],
+        "ObrasSociales",
+        &["IDObraSocial"],
+    ),
+    fk("DependenciasEmpresas", DIRECCION, "Direcciones", DIRECCION),
+    fk(
+        "DependenciasEmpresas",
+        &["DNIProfesor"],
+        "Profesores",
+        &["DNI"],
+    ),
+    fk(
+        "DeclaracionesDeCargo",
+        &["DNIProfesor", "NombreDep"],
+        "DependenciasEmpresas",
+        &["DNIProfesor", "Nombre"],
+    ),
+    fk(
+        "AntecedentesProfesionales",
+        &["IDDeclaracion"],
+        "DeclaracionesDeCargo",
+        &["IDDeclaracion"],
+    ),
+    fk(
+        "AntecedentesProfesionales",
+        &["DNIProfesor"],
+        "Profesores",
+        &["DNI"],
+    ),
+    fk(
+        "ReferenciaBibliografica",
+        &["IDFuente"],
+        "Publicaciones",
+        &["IDPublicacion"],
+    ),
+    fk(
+        "ReferenciaBibliografica",
+        &["IDCitador"],
+        "Publicaciones",
+        &["IDPublicacion"],
+    ),
+    fk(
+        "Publico",
+        &["IDPublicacion"],
+        "Publicaciones",
+        &["IDPublicacion"],
+    ),
+    fk("Publico", &["DNIProfesor"], "Profesores", &["DNI"]),
+    fk(
+        "ParticipoEnReunion",
+        &["DNIProfesor"],
+        "Profesores",
+        &["DNI"],
+    ),
+    fk(
+        "ParticipoEnReunion",
+        &["Titulo", "Fecha"],
+        "ReunionesCientificas",
+        &["Titulo", "Fecha"],
+    ),
+    fk(
+        "Familiares",
+        &["CodigoPostal", "Calle", "NumeroDir"],
+        "Direcciones",
+        DIRECCION,
+    ),
+    fk("Familiares", &["DNIProfesor"], "Profesores", &["DNI"]),
+    fk(
+        "Beneficia",
+        &["IDObraSocial"],
+        "ObrasSociales",
+        &["IDObraSocial"],
+    ),
+    fk(
+        "Beneficia",
+        &["DNIFamiliar", "DNIProfesor"],
+        "Familiares",
+        &["DNIFamiliar", "DNIProfesor"],
+    ),
+    fk(
+        "DocObraSocial",
+        &["IDObraSocial"],
+        "ObrasSociales",
+        &["IDObraSocial"],
+    ),
+    Referencia {
+        tabla: "DocObraSocial",
+        columnas: &["DNIProfesor"],
+        referida: "Profesores",
+        columnas_referidas: &["DNI"],
+        declarada: false,
+    },
+    fk("PercibeEn", &["DNI"], "Profesores", &["DNI"]),
+    fk(
+        "PercibeEn",
+        &["Tipo", "InstitucionCaja"],
+        "Percepciones",
+        &["Tipo", "InstitucionCaja"],
+    ),
+    fk(
+        "DeclaracionesJuradas",
+        &["DNIProfesor"],
+        "Profesores",
+        &["DNI"],
+    ),
+    fk(
+        "AntecedentesDocentes",
+        &["NombreInst"],
+        "Instituciones",
+        &["Nombre"],
+    ),
+    fk(
+        "AntecedentesDocentes",
+        &["IDDeclaracion"],
+        "DeclaracionesDeCargo",
+        &["IDDeclaracion"],
+    ),
+    fk(
+        "AntecedentesDocentes",
+        &["DNIProfesor"],
+        "Profesores",
+        &["DNI"],
+    ),
+    fk(
+        "Horarios",
+        &["IDDeclaracion"],
+        "DeclaracionesDeCargo",
+        &["IDDeclaracion"],
+    ),
+    fk("Empleadores", DIRECCION, "Direcciones", DIRECCION),
+    fk("ResideEn", DIRECCION, "Direcciones", DIRECCION),
+    fk("ResideEn", &["DNIProfesor"], "Profesores", &["DNI"]),
+    fk(
+        "AseguraA",
+        &["CodigoCompania"],
+        "Seguros",
+        &["CodigoCompania"],
+    ),
+    fk(
+        "AseguraA",
+        &["DNIFamiliar", "DNIProfesor"],
+        "Familiares",
+        &["DNIFamiliar", "DNIProfesor"],
+    ),
+    fk("Profesores", &["CUITEmpleador"], "Empleadores", &["CUIT"]),
+];
+
+/// Migración de MySQL, de la cual se leen los tipos de las columnas.
+const MIGRACION: &str = include_str!("../migrations/20240606032226_cargar_tablas.sql");
+
+/// Una columna de una tabla de la migración de MySQL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Columna {
+    pub nombre: String,
+    /// Tipo en minúsculas tal como figura en la migración, por ejemplo `varchar(100)`.
+    pub tipo: String,
+}
+
+/// Separa `texto` en las comas que no están entre paréntesis.
+fn separar(texto: &str) -> Vec<&str> {
+    let mut partes = Vec::new();
+    let (mut nivel, mut inicio) = (0, 0);
+    for (i, c) in texto.char_indices() {
+        match c {
+            '(' => nivel += 1,
+            ')' => nivel -= 1,
+            ',' if nivel == 0 => {
+                partes.push(&texto[inicio..i]);
+                inicio = i + 1;
+            }
+            _ => {}
+        }
+    }
+    partes.push(&texto[inicio..]);
+    partes
+}
+
+/// Columnas de cada tabla de la migración de MySQL, que se interpreta la primera vez que se
+/// consultan.
+static COLUMNAS: OnceLock<HashMap<String, Vec<Columna>>> = OnceLock::new();
+
+/// Devuelve las columnas de `tabla` según la migración de MySQL, o un vector vacío si la tabla no
+/// existe.
+pub fn columnas(tabla: &str) -> Vec<Columna> {
+    COLUMNAS
+        .get_or_init(leer_migracion)
+        .get(tabla)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Interpreta las sentencias `CREATE TABLE` de la migración de MySQL.
+fn leer_migracion() -> HashMap<String, Vec<Columna>> {
+    let migracion: String = MIGRACION
+        .lines()
+        .map(|linea| linea.split("--").next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut tablas = HashMap::new();
+    for (inicio, encabezado) in migracion.match_indices("CREATE TABLE") {
+        let resto = migracion[inicio + encabezado.len()..].trim_start();
+        let fin = resto
+            .find(|c: char| c == '(' || c.is_whitespace())
+            .unwrap_or(resto.len());
+        let (tabla, resto) = resto.split_at(fin);
+        let resto = resto.trim_start();
+        if !resto.starts_with('(') {
+            continue;
+        }
+        let mut nivel = 0;
+        let cierre = resto
+            .char_indices()
+            .find(|&(_, c)| {
+                match c {
+                    '(' => nivel += 1,
+                    ')' => nivel -= 1,
+                    _ => {}
+                }
+                nivel == 0
+            })
+            .map_or(resto.len(), |(i, _)| i);
+        tablas
+            .entry(tabla.to_string())
+            .or_insert_with(|| definiciones(&resto[1..cierre]));
+    }
+    tablas
+}
+
+/// Interpreta las definiciones de columnas del cuerpo de un `CREATE TABLE`, omitiendo las
+/// restricciones.
+fn definiciones(cuerpo: &str) -> Vec<Columna> {
+    separar(cuerpo)
+        .into_iter()
+        .filter_map(|definicion| {
+            let definicion = definicion.trim();
+            let (nombre, tipo) = definicion.split_once(char::is_whitespace)?;
+            let es_restriccion = ["PRIMARY", "FOREIGN", "CHECK", "CONSTRAINT", "UNIQUE", "KEY"]
+                .iter()
+                .any(|r| nombre.eq_ignore_ascii_case(r) || nombre.starts_with("CHECK("));
+            if es_restriccion {
+                return None;
+            }
+            // El tipo termina en el primer espacio fuera de los paréntesis, sin contar los que
+            // los preceden, como en `char (8)`.
+            let tipo = tipo.trim_start().replace(" (", "(");
+            let mut nivel = 0;
+            let fin = tipo
+                .char_indices()
+                .find(|&(_, c)| {
+                    match c {
+                        '(' => nivel += 1,
+                        ')' => nivel -= 1,
+                        _ => {}
+                    }
+                    nivel == 0 && c.is_whitespace()
+                })
+                .map_or(tipo.len(), |(i, _)| i);
+            Some(Columna {
+                nombre: nombre.to_string(),
+                tipo: tipo[..fin].to_lowercase(),
+            })
+        })
+        .collect()
+}
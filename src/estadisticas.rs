@@ -0,0 +1,119 @@
+// Autor: Acosta Quintana, Lautaro
+use serde::Serialize;
+use sqlx::{MySql, Pool};
+
+use crate::esquema::TABLAS;
+
+/// Tipos de columna para los que se informa el mínimo y el máximo.
+const TIPOS_ORDENABLES: [&str; 12] = [
+    "date",
+    "datetime",
+    "timestamp",
+    "time",
+    "year",
+    "tinyint",
+    "smallint",
+    "mediumint",
+    "int",
+    "bigint",
+    "decimal",
+    "double",
+];
+
+/// Estadísticas de una columna de una tabla ya cargada.
+#[derive(Debug, Serialize)]
+pub struct EstadisticaColumna {
+    pub nombre: String,
+    pub tipo: String,
+    pub nulable: bool,
+    /// Proporción de filas con la columna en NULL, sólo para las columnas que lo admiten.
+    pub proporcion_nulos: Option<f64>,
+    pub distintos: i64,
+    /// Sólo para fechas, horas y números.
+    pub minimo: Option<String>,
+    pub maximo: Option<String>,
+    /// Valores más frecuentes junto a su cantidad de apariciones, sólo para los enums.
+    pub mas_frecuentes: Vec<(String, i64)>,
+}
+
+/// Estadísticas de una tabla ya cargada.
+#[derive(Debug, Serialize)]
+pub struct EstadisticaTabla {
+    pub tabla: String,
+    pub filas: i64,
+    pub columnas: Vec<EstadisticaColumna>,
+}
+
+/// Calcula las estadísticas de cada columna de cada una de las tablas del esquema, informando los
+/// `top` valores más frecuentes de las columnas enum.
+pub async fn estadisticas(pool: &Pool<MySql>, top: usize) -> anyhow::Result<Vec<EstadisticaTabla>> {
+    let mut tablas = Vec::with_capacity(TABLAS.len());
+    for tabla in TABLAS {
+        let filas: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {tabla}"))
+            .fetch_one(pool)
+            .await?;
+
+        // Se convierten a CHAR porque information_schema devuelve las cadenas como binarias en
+        // algunas versiones de MySQL.
+        let definiciones: Vec<(String, String, i64)> = sqlx::query_as(
+            "SELECT CAST(COLUMN_NAME AS CHAR), CAST(DATA_TYPE AS CHAR), CAST(IS_NULLABLE = 'YES' AS SIGNED)
+            FROM information_schema.COLUMNS
+            WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?
+            ORDER BY ORDINAL_POSITION",
+        )
+        .bind(tabla)
+        .fetch_all(pool)
+        .await?;
+
+        let mut columnas = Vec::with_capacity(definiciones.len());
+        for (nombre, tipo, nulable) in definiciones {
+            let nulable = nulable != 0;
+            let (min_sql, max_sql) = if TIPOS_ORDENABLES.contains(&tipo.as_str()) {
+                (
+                    format!("CAST(MIN(`{nombre}`) AS CHAR)"),
+                    format!("CAST(MAX(`{nombre}`) AS CHAR)"),
+                )
+            } else {
+                ("NULL".to_string(), "NULL".to_string())
+            };
+            let consulta = format!(
+                "SELECT COUNT(DISTINCT `{nombre}`), CAST(COALESCE(SUM(`{nombre}` IS NULL), 0) AS SIGNED),
+                    {min_sql}, {max_sql}
+                FROM {tabla}"
+            );
+            let (distintos, nulos, minimo, maximo): (i64, i64, Option<String>, Option<String>) =
+                sqlx::query_as(&consulta).fetch_one(pool).await?;
+
+            let mas_frecuentes = if tipo == "enum" {
+                sqlx::query_as(&format!(
+                    "SELECT CAST(`{nombre}` AS CHAR), COUNT(*) AS n FROM {tabla}
+                    WHERE `{nombre}` IS NOT NULL
+                    GROUP BY `{nombre}` ORDER BY n DESC LIMIT ?"
+                ))
+                .bind(top as u64)
+                .fetch_all(pool)
+                .await?
+            } else {
+                Vec::new()
+            };
+
+            columnas.push(EstadisticaColumna {
+                nombre,
+                tipo,
+                nulable,
+                proporcion_nulos: (nulable && filas > 0).then(|| nulos as f64 / filas as f64),
+                distintos,
+                minimo,
+                maximo,
+                mas_frecuentes,
+            });
+        }
+
+        tablas.push(EstadisticaTabla {
+            tabla: tabla.to_string(),
+            filas,
+            columnas,
+        });
+    }
+    Ok(tablas)
+}
@@ -0,0 +1,256 @@
+// Autor: Acosta Quintana, Lautaro
+//! Generación y carga de los datos de todas las tablas desde otros programas.
+//!
+//! [`Generador`] reúne los pasos de una carga: controla que la base de datos se pueda cargar,
+//! ejecuta las migraciones, deja constancia de la carga, genera e inserta las filas e informa el
+//! resultado. Por ejemplo, para cargar siempre las mismas filas con 500 profesores:
+//!
+//! ```text
+//! let generacion = Generador::new(conexion)
+//!     .semilla(42)
+//!     .cantidad("Profesores", 500)
+//!     .ejecutar()
+//!     .await?;
+//! ```
+//!
+//! Además de la base de datos, las filas se pueden enviar a otros destinos, como un respaldo en
+//! SQL, con [`Generador::destino`].
+
+use std::sync::Arc;
+
+use dbdata::Conexion;
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
+
+use crate::{
+    cardinalidad::Cardinalidad,
+    carga::{self, Generados, OpcionesCarga, TABLAS_CON_CANTIDAD},
+    citas::Citas,
+    datasets::{Datasets, FuenteDatasets},
+    destino::{Destino, Destinos},
+    distribucion::Distribucion,
+    guardia::{inspeccionar, marcar, BaseRechazada, EstadoCarga, BASES_PERMITIDAS},
+    masiva::{activar_carga_masiva, desactivar_carga_masiva, ConexionInfile},
+    migrar, notificar_carga,
+    reintentos::{configurar_reintentos, Reintentos},
+    tabla_interrumpida, ContadorGlobal, ConteoTabla, Interrupcion, Notificacion, CONTADOR,
+};
+
+/// Se toma durante toda la carga, porque el generador de datos, los conteos y la carga masiva son
+/// globales y dos cargas a la vez mezclarían sus datos.
+static EJECUCION: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Resultado de una carga.
+#[derive(Debug, Clone)]
+pub struct Generacion {
+    /// Filas de cada tabla que el destino aceptó.
+    pub filas: Generados,
+    /// Filas cargadas, rechazadas y reintentadas en cada tabla, en el orden en que se cargaron.
+    pub conteos: Vec<ConteoTabla>,
+    /// Semilla con la que se generaron los datos, para volver a generarlos.
+    pub semilla: u64,
+}
+
+/// Configuración de una carga. Se crea con [`Generador::new`], se configura encadenando sus
+/// métodos y se ejecuta con [`Generador::ejecutar`].
+#[derive(Clone)]
+pub struct Generador {
+    conexion: Conexion,
+    /// Destinos a los que se envían las filas además de la base de datos.
+    destinos: Vec<Arc<dyn Destino>>,
+    opciones: OpcionesCarga,
+    datasets: FuenteDatasets,
+    reintentos: Reintentos,
+    masiva: bool,
+    infile: Option<ConexionInfile>,
+    bases_permitidas: Vec<String>,
+    forzar: bool,
+}
+
+impl Generador {
+    pub fn new(conexion: Conexion) -> Self {
+        Self {
+            conexion,
+            destinos: Vec::new(),
+            opciones: OpcionesCarga::default(),
+            datasets: FuenteDatasets::default(),
+            reintentos: Reintentos::default(),
+            masiva: false,
+            infile: None,
+            bases_permitidas: BASES_PERMITIDAS.iter().map(|p| p.to_string()).collect(),
+            forzar: false,
+        }
+    }
+
+    /// Reemplaza todas las opciones de generación, incluidas la semilla y las cantidades.
+    pub fn opciones(mut self, opciones: OpcionesCarga) -> Self {
+        self.opciones = opciones;
+        self
+    }
+
+    /// Cantidad de registros a generar en cada tabla que no tenga su propia [`Self::cantidad`].
+    pub fn muestras(mut self, muestras: usize) -> Self {
+        self.opciones.muestras = muestras;
+        self
+    }
+
+    /// Cantidad de registros a generar en `tabla`, que debe ser una de las
+    /// [`TABLAS_CON_CANTIDAD`].
+    pub fn cantidad(mut self, tabla: &str, cantidad: usize) -> Self {
+        self.opciones.cantidades.insert(tabla.to_string(), cantidad);
+        self
+    }
+
+    /// Semilla con la que se generan los datos; sin ella se elige una al azar.
+    pub fn semilla(mut self, semilla: u64) -> Self {
+        self.opciones.semilla = Some(semilla);
+        self
+    }
+
+    /// Proporción, entre 0 y 1, de obras sociales, aseguradoras y cajas previsionales inventadas
+    /// en lugar de tomadas de los datasets; por defecto sólo se inventan cuando se agotan.
+    pub fn ficticias(mut self, proporcion: f64) -> Self {
+        self.opciones.ficticias = proporcion;
+        self
+    }
+
+    /// Cantidad de referencias bibliográficas de cada publicación y cómo se eligen sus fuentes.
+    pub fn citas(mut self, citas: Citas) -> Self {
+        self.opciones.citas = citas;
+        self
+    }
+
+    pub fn distribucion(mut self, relacion: &str, distribucion: Distribucion) -> Self {
+        self.opciones
+            .distribuciones
+            .configurar(relacion, distribucion);
+        self
+    }
+
+    pub fn cardinalidad(mut self, cardinalidad: Cardinalidad) -> Self {
+        self.opciones.cardinalidades.configurar(cardinalidad);
+        self
+    }
+
+    /// Envía también a `destino` las filas que la base de datos aceptó (ver [`Destinos`]).
+    pub fn destino(mut self, destino: Arc<dyn Destino>) -> Self {
+        self.destinos.push(destino);
+        self
+    }
+
+    /// De dónde se leen los datasets; por defecto se usan los incluidos en el binario.
+    pub fn datasets(mut self, datasets: FuenteDatasets) -> Self {
+        self.datasets = datasets;
+        self
+    }
+
+    pub fn reintentos(mut self, reintentos: Reintentos) -> Self {
+        self.reintentos = reintentos;
+        self
+    }
+
+    /// Envía las filas de cada tabla juntas (ver [`crate::masiva`]).
+    pub fn masiva(mut self, masiva: bool) -> Self {
+        self.masiva = masiva;
+        self
+    }
+
+    /// Conexión de MySQL con la que la carga masiva usa `LOAD DATA LOCAL INFILE`.
+    pub fn infile(mut self, conexion: ConexionInfile) -> Self {
+        self.infile = Some(conexion);
+        self
+    }
+
+    /// Patrones de los nombres de las bases de datos que se pueden cargar; por defecto
+    /// [`BASES_PERMITIDAS`].
+    pub fn bases_permitidas(mut self, permitidas: Vec<String>) -> Self {
+        self.bases_permitidas = permitidas;
+        self
+    }
+
+    /// Carga la base de datos aunque no esté vacía o su nombre no esté permitido.
+    pub fn forzar(mut self, forzar: bool) -> Self {
+        self.forzar = forzar;
+        self
+    }
+
+    /// Carga la base de datos y devuelve las filas generadas y los conteos de cada tabla.
+    ///
+    /// Falla con [`BaseRechazada`] si la base de datos no se puede cargar sin [`Self::forzar`],
+    /// y con [`Interrupcion`] si se interrumpió la carga; en ese caso los conteos hasta el momento
+    /// quedan en [`CONTADOR`].
+    ///
+    /// Si ya hay otra carga en curso en el mismo programa, espera a que termine.
+    pub async fn ejecutar(self) -> anyhow::Result<Generacion> {
+        let _ejecucion = EJECUCION.lock().await;
+        if let Some(tabla) = self
+            .opciones
+            .cantidades
+            .keys()
+            .find(|t| !TABLAS_CON_CANTIDAD.contains(&t.as_str()))
+        {
+            anyhow::bail!(
+                "No se puede indicar la cantidad de filas de la tabla '{tabla}'. Se puede indicar \
+                la de: {}.",
+                TABLAS_CON_CANTIDAD.join(", ")
+            );
+        }
+        let datasets = Datasets::cargar(&self.datasets)?;
+
+        let conexion = &self.conexion;
+        let problemas = inspeccionar(conexion)
+            .await?
+            .problemas(&self.bases_permitidas);
+        if !problemas.is_empty() {
+            if !self.forzar {
+                return Err(BaseRechazada { problemas }.into());
+            }
+            for problema in &problemas {
+                notificar_carga(Notificacion::WARN, problema);
+            }
+        }
+
+        configurar_reintentos(self.reintentos);
+        if self.masiva {
+            let infile = match conexion {
+                Conexion::MySql(_) => self.infile.clone(),
+                _ => None,
+            };
+            activar_carga_masiva(infile).await;
+        } else {
+            desactivar_carga_masiva().await;
+        }
+        migrar(conexion).await?;
+
+        let semilla = self.opciones.semilla.unwrap_or_else(rand::random);
+        let opciones = OpcionesCarga {
+            semilla: Some(semilla),
+            ..self.opciones.clone()
+        };
+        let marca = marcar(conexion, opciones.muestras).await?;
+        *CONTADOR.lock().await = ContadorGlobal::new();
+        let mut destinos: Vec<Arc<dyn Destino>> = vec![Arc::new(conexion.clone())];
+        destinos.extend(self.destinos.iter().cloned());
+        let filas = match carga::cargar(&Destinos(destinos), datasets, &opciones).await {
+            Ok(filas) => filas,
+            Err(err) => {
+                let (estado, ultima) = if err.is::<Interrupcion>() {
+                    (EstadoCarga::Interrumpida, tabla_interrumpida().await)
+                } else {
+                    (EstadoCarga::Fallida, None)
+                };
+                marca.actualizar(conexion, estado, ultima).await?;
+                return Err(err);
+            }
+        };
+        marca
+            .actualizar(conexion, EstadoCarga::Completa, None)
+            .await?;
+
+        Ok(Generacion {
+            filas,
+            conteos: CONTADOR.lock().await.get_por_tabla(),
+            semilla,
+        })
+    }
+}
@@ -0,0 +1,365 @@
+// Autor: Acosta Quintana, Lautaro
+//! Controles que se hacen antes de escribir en la base de datos para no cargar datos en una base
+//! de datos equivocada.
+
+use dbdata::{Conexion, Valor};
+use serde::Serialize;
+
+use crate::{contar_filas, esquema::TABLAS, migrador};
+
+/// Tabla en la que el generador deja constancia de cada carga.
+pub const TABLA_MARCA: &str = "CargaDatos";
+
+/// Patrones de los nombres de las bases de datos en las que se puede cargar sin `--forzar`. El
+/// `*` representa cualquier secuencia de caracteres y no se distinguen mayúsculas de minúsculas.
+pub const BASES_PERMITIDAS: [&str; 5] = ["tpi*", "*test*", "*prueba*", "*dev*", ":memory:"];
+
+/// Estado de la base de datos de destino antes de la carga.
+#[derive(Debug, Clone, Serialize)]
+pub struct Inspeccion {
+    /// Nombre de la base de datos, o de su archivo en SQLite.
+    pub nombre: String,
+    /// `true` si la base de datos tiene la tabla de [`TABLA_MARCA`], es decir, si ya fue cargada
+    /// por el generador.
+    pub marcada: bool,
+    /// Tablas del esquema que ya tienen filas, con su cantidad.
+    pub filas: Vec<(String, i64)>,
+    /// Tablas que no pertenecen al esquema.
+    pub ajenas: Vec<String>,
+    /// Cantidad de migraciones que todavía no se ejecutaron.
+    pub migraciones_pendientes: usize,
+    /// Última carga registrada en [`TABLA_MARCA`].
+    pub ultima_carga: Option<UltimaCarga>,
+}
+
+/// Una carga registrada en [`TABLA_MARCA`].
+#[derive(Debug, Clone, Serialize)]
+pub struct UltimaCarga {
+    pub fecha: String,
+    pub muestras: i64,
+    pub estado: String,
+    /// Tabla en la que terminó la carga si se interrumpió.
+    pub ultima_tabla: Option<String>,
+}
+
+impl std::fmt::Display for UltimaCarga {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} del {} con {} muestras",
+            self.estado, self.fecha, self.muestras
+        )?;
+        match &self.ultima_tabla {
+            Some(tabla) => write!(f, " (en la tabla {tabla})"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Inspeccion {
+    pub fn esta_vacia(&self) -> bool {
+        self.filas.is_empty()
+    }
+
+    /// Motivos por los cuales no se debería escribir en la base de datos sin `--forzar`. Una base
+    /// de datos marcada por el generador se permite aunque su nombre no coincida con `permitidas`.
+    pub fn problemas(&self, permitidas: &[String]) -> Vec<String> {
+        let mut problemas = Vec::new();
+        if !self.marcada && !permitidas.iter().any(|p| coincide(p, &self.nombre)) {
+            problemas.push(format!(
+                "El nombre de la base de datos '{}' no coincide con ninguno de los permitidos ({}).",
+                self.nombre,
+                permitidas.join(", ")
+            ));
+        }
+        if !self.esta_vacia() {
+            let total: i64 = self.filas.iter().map(|(_, filas)| filas).sum();
+            problemas.push(format!(
+                "La base de datos no está vacía: {total} filas en {} tablas{}.",
+                self.filas.len(),
+                match (&self.ultima_carga, self.marcada) {
+                    (Some(carga), _) => format!(" de una carga anterior del generador, {carga}"),
+                    (None, true) => " de una carga anterior del generador".to_string(),
+                    (None, false) => " que no fueron cargadas por el generador".to_string(),
+                }
+            ));
+        }
+        if !self.ajenas.is_empty() {
+            problemas.push(format!(
+                "La base de datos contiene tablas que no pertenecen al esquema: {}.",
+                self.ajenas.join(", ")
+            ));
+        }
+        problemas
+    }
+}
+
+/// Indica si `nombre` coincide con `patron`, donde `*` representa cualquier secuencia de
+/// caracteres.
+pub fn coincide(patron: &str, nombre: &str) -> bool {
+    let patron = patron.to_lowercase();
+    let nombre = nombre.to_lowercase();
+    let partes: Vec<&str> = patron.split('*').collect();
+    let (primera, resto) = partes.split_first().unwrap();
+    let Some(mut restante) = nombre.strip_prefix(primera) else {
+        return false;
+    };
+    let Some((ultima, medio)) = resto.split_last() else {
+        return restante.is_empty();
+    };
+    for parte in medio {
+        match restante.find(parte) {
+            Some(i) => restante = &restante[i + parte.len()..],
+            None => return false,
+        }
+    }
+    restante.len() >= ultima.len() && restante.ends_with(ultima)
+}
+
+/// Obtiene el nombre de la base de datos y sus tablas sin escribir en ella.
+async fn nombre_y_tablas(conexion: &Conexion) -> anyhow::Result<(String, Vec<String>)> {
+    Ok(match conexion {
+        Conexion::MySql(pool) => {
+            let nombre: Option<String> = sqlx::query_scalar("SELECT DATABASE()")
+                .fetch_one(pool)
+                .await?;
+            let tablas = sqlx::query_scalar(
+                "SELECT CAST(TABLE_NAME AS CHAR) FROM information_schema.TABLES
+                WHERE TABLE_SCHEMA = DATABASE()",
+            )
+            .fetch_all(pool)
+            .await?;
+            (nombre.unwrap_or_default(), tablas)
+        }
+        Conexion::Postgres(pool) => {
+            let nombre = sqlx::query_scalar("SELECT current_database()::text")
+                .fetch_one(pool)
+                .await?;
+            let tablas = sqlx::query_scalar(
+                "SELECT tablename::text FROM pg_tables WHERE schemaname = current_schema()",
+            )
+            .fetch_all(pool)
+            .await?;
+            (nombre, tablas)
+        }
+        Conexion::Sqlite(pool) => {
+            let archivo: String =
+                sqlx::query_scalar("SELECT file FROM pragma_database_list WHERE name = 'main'")
+                    .fetch_one(pool)
+                    .await?;
+            let nombre = match std::path::Path::new(&archivo).file_stem() {
+                Some(nombre) => nombre.to_string_lossy().into_owned(),
+                None => ":memory:".to_string(),
+            };
+            let tablas = sqlx::query_scalar(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+            )
+            .fetch_all(pool)
+            .await?;
+            (nombre, tablas)
+        }
+    })
+}
+
+/// Lee la última carga registrada en [`TABLA_MARCA`]. Las tablas creadas por versiones
+/// anteriores, que sólo tienen la versión y la fecha, se ignoran.
+async fn ultima_carga(conexion: &Conexion) -> anyhow::Result<Option<UltimaCarga>> {
+    type Fila = (String, i64, String, Option<String>);
+    let consulta = format!(
+        "SELECT Fecha, Muestras, Estado, UltimaTabla FROM {TABLA_MARCA} ORDER BY Fecha DESC LIMIT 1"
+    );
+    let fila: Result<Option<Fila>, sqlx::Error> = match conexion {
+        Conexion::MySql(pool) => sqlx::query_as(&consulta).fetch_optional(pool).await,
+        Conexion::Postgres(pool) => sqlx::query_as(&consulta).fetch_optional(pool).await,
+        Conexion::Sqlite(pool) => sqlx::query_as(&consulta).fetch_optional(pool).await,
+    };
+    Ok(match fila {
+        Ok(fila) => fila.map(|(fecha, muestras, estado, ultima_tabla)| UltimaCarga {
+            fecha,
+            muestras,
+            estado,
+            ultima_tabla,
+        }),
+        Err(sqlx::Error::Database(_)) => None,
+        Err(e) => return Err(e.into()),
+    })
+}
+
+/// Revisa la base de datos de destino sin escribir en ella.
+pub async fn inspeccionar(conexion: &Conexion) -> anyhow::Result<Inspeccion> {
+    let (nombre, tablas) = nombre_y_tablas(conexion).await?;
+    // Postgres guarda en minúsculas los nombres de las tablas creadas sin comillas.
+    let existe = |tabla: &str| tablas.iter().any(|t| t.eq_ignore_ascii_case(tabla));
+
+    let mut filas = Vec::new();
+    for tabla in TABLAS.iter().copied().filter(|t| existe(t)) {
+        let cantidad = contar_filas(conexion, tabla).await?;
+        if cantidad > 0 {
+            filas.push((tabla.to_string(), cantidad));
+        }
+    }
+
+    let ajenas = tablas
+        .iter()
+        .filter(|t| {
+            !TABLAS.iter().any(|tabla| t.eq_ignore_ascii_case(tabla))
+                && !t.eq_ignore_ascii_case(TABLA_MARCA)
+                && t.as_str() != "_sqlx_migrations"
+        })
+        .cloned()
+        .collect();
+
+    let aplicadas: Vec<i64> = if existe("_sqlx_migrations") {
+        let consulta = "SELECT version FROM _sqlx_migrations";
+        match conexion {
+            Conexion::MySql(pool) => sqlx::query_scalar(consulta).fetch_all(pool).await?,
+            Conexion::Postgres(pool) => sqlx::query_scalar(consulta).fetch_all(pool).await?,
+            Conexion::Sqlite(pool) => sqlx::query_scalar(consulta).fetch_all(pool).await?,
+        }
+    } else {
+        Vec::new()
+    };
+    let migraciones_pendientes = migrador(conexion)
+        .iter()
+        .filter(|m| !aplicadas.contains(&m.version))
+        .count();
+
+    let ultima_carga = if existe(TABLA_MARCA) {
+        ultima_carga(conexion).await?
+    } else {
+        None
+    };
+
+    Ok(Inspeccion {
+        nombre,
+        marcada: existe(TABLA_MARCA),
+        ultima_carga,
+        filas,
+        ajenas,
+        migraciones_pendientes,
+    })
+}
+
+/// Estado de una carga registrado en [`TABLA_MARCA`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EstadoCarga {
+    EnCurso,
+    Completa,
+    /// Se interrumpió con Ctrl-C; la columna `UltimaTabla` indica la tabla que se estaba
+    /// cargando, por lo que las anteriores están completas.
+    Interrumpida,
+    Fallida,
+}
+
+impl EstadoCarga {
+    pub fn como_str(&self) -> &'static str {
+        match self {
+            EstadoCarga::EnCurso => "en curso",
+            EstadoCarga::Completa => "completa",
+            EstadoCarga::Interrumpida => "interrumpida",
+            EstadoCarga::Fallida => "fallida",
+        }
+    }
+}
+
+/// Ejecuta una consulta que no devuelve filas.
+async fn ejecutar(conexion: &Conexion, consulta: &str) -> anyhow::Result<()> {
+    match conexion {
+        Conexion::MySql(pool) => {
+            sqlx::query(consulta).execute(pool).await?;
+        }
+        Conexion::Postgres(pool) => {
+            sqlx::query(consulta).execute(pool).await?;
+        }
+        Conexion::Sqlite(pool) => {
+            sqlx::query(consulta).execute(pool).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Error con el que se rechaza una carga por los [`Inspeccion::problemas`] de la base de datos.
+#[derive(Debug, Clone)]
+pub struct BaseRechazada {
+    pub problemas: Vec<String>,
+}
+
+impl std::fmt::Display for BaseRechazada {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "No se cargó la base de datos: {}",
+            self.problemas.join(" ")
+        )
+    }
+}
+
+impl std::error::Error for BaseRechazada {}
+
+/// Fila de [`TABLA_MARCA`] correspondiente a una carga.
+#[derive(Debug, Clone)]
+pub struct Marca {
+    /// Identificador al azar de la fila, ya que dos cargas pueden empezar en el mismo instante.
+    id: i64,
+}
+
+impl Marca {
+    /// Actualiza el estado de la carga y la tabla en la que terminó, que sirve para reanudarla.
+    pub async fn actualizar(
+        &self,
+        conexion: &Conexion,
+        estado: EstadoCarga,
+        ultima_tabla: Option<&str>,
+    ) -> anyhow::Result<()> {
+        conexion
+            .ejecutar(
+                &format!("UPDATE {TABLA_MARCA} SET Estado = ?, UltimaTabla = ? WHERE Id = ?"),
+                &[
+                    Valor::Texto(estado.como_str().to_string()),
+                    ultima_tabla.map_or(Valor::Nulo, |t| Valor::Texto(t.to_string())),
+                    Valor::Entero(self.id),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// Deja constancia en [`TABLA_MARCA`] de que el generador empezó a cargar la base de datos con
+/// `muestras` registros por tabla.
+pub async fn marcar(conexion: &Conexion, muestras: usize) -> anyhow::Result<Marca> {
+    let fecha = chrono::Local::now()
+        .format("%Y-%m-%d %H:%M:%S%.3f")
+        .to_string();
+    ejecutar(
+        conexion,
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {TABLA_MARCA} (
+                Id bigint NOT NULL PRIMARY KEY,
+                Version varchar(20) NOT NULL,
+                Fecha varchar(30) NOT NULL,
+                Muestras bigint NOT NULL,
+                Estado varchar(20) NOT NULL,
+                UltimaTabla varchar(50)
+            )"
+        ),
+    )
+    .await?;
+    let id = rand::random::<i64>();
+    conexion
+        .ejecutar(
+            &format!(
+                "INSERT INTO {TABLA_MARCA} (Id, Version, Fecha, Muestras, Estado)
+                VALUES (?, ?, ?, ?, ?)"
+            ),
+            &[
+                Valor::Entero(id),
+                Valor::Texto(env!("CARGO_PKG_VERSION").to_string()),
+                Valor::Texto(fecha),
+                Valor::Entero(muestras as i64),
+                Valor::Texto(EstadoCarga::EnCurso.como_str().to_string()),
+            ],
+        )
+        .await?;
+    Ok(Marca { id })
+}
@@ -1,43 +1,171 @@
 // Autor: Acosta Quintana, Lautaro
 use colored::Colorize;
-use dbdata::DBData;
-use fake::{Fake, Faker};
-use sqlx::{mysql::MySqlPoolOptions, MySql, Pool};
+use dbdata::{Conexion, Insertable};
 
+pub mod auditoria;
+pub mod cardinalidad;
+pub mod carga;
+pub mod citas;
+pub mod conexion;
 pub mod datasets;
 pub mod db_tablas;
+pub mod destino;
+pub mod distribucion;
+pub mod esquema;
+pub mod estadisticas;
+pub mod generador;
+pub mod guardia;
+pub mod masiva;
+pub mod plan;
+#[cfg(feature = "prueba")]
+pub mod prueba;
+pub mod reintentos;
 
 use once_cell::sync::Lazy;
-use std::collections::HashMap;
+use sqlx::migrate::Migrator;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
 use tokio::sync::Mutex;
 
-/// Establece una conexión con la base de datos utilizando el URL definido en las variables del
-/// ambiente.
-pub async fn conectar_con_bd() -> anyhow::Result<Pool<MySql>> {
-    dotenvy::dotenv().expect("Archivo .env no pudo se encontrado.");
-    let db_url =
-        std::env::var("DATABASE_URL").expect("No se pudo encontrar la variable 'DATABASE_URL'.");
-    Ok(MySqlPoolOptions::new()
-        .acquire_timeout(std::time::Duration::from_secs(4))
-        .connect(&db_url)
-        .await?)
-}
-
-/// Genera e inserta dentro de la base de datos los datos generados completamente de manera
-/// pseudoaleatoria.
-pub async fn cargar_tabla<T>(muestras: usize, pool: &Pool<MySql>) -> anyhow::Result<Vec<T>>
+use destino::Destino;
+use esquema::TABLAS;
+
+/// Migraciones correspondientes al motor de la conexión.
+pub fn migrador(conexion: &Conexion) -> Migrator {
+    match conexion {
+        Conexion::MySql(_) => sqlx::migrate!("./migrations"),
+        Conexion::Postgres(_) => sqlx::migrate!("./migrations_postgres"),
+        Conexion::Sqlite(_) => sqlx::migrate!("./migrations_sqlite"),
+    }
+}
+
+/// Crea las tablas ejecutando la migración correspondiente al motor de la conexión.
+pub async fn migrar(conexion: &Conexion) -> anyhow::Result<()> {
+    let migrador = migrador(conexion);
+    match conexion {
+        Conexion::MySql(pool) => migrador.run(pool).await?,
+        Conexion::Postgres(pool) => migrador.run(pool).await?,
+        Conexion::Sqlite(pool) => migrador.run(pool).await?,
+    }
+    Ok(())
+}
+
+/// Cuenta las filas de `tabla`.
+pub async fn contar_filas(conexion: &Conexion, tabla: &str) -> anyhow::Result<i64> {
+    let consulta = format!("SELECT COUNT(*) FROM {tabla}");
+    Ok(match conexion {
+        Conexion::MySql(pool) => sqlx::query_scalar(&consulta).fetch_one(pool).await?,
+        Conexion::Postgres(pool) => sqlx::query_scalar(&consulta).fetch_one(pool).await?,
+        Conexion::Sqlite(pool) => sqlx::query_scalar(&consulta).fetch_one(pool).await?,
+    })
+}
+
+/// Se pone en `true` al recibir Ctrl-C para que la carga deje de insertar filas.
+pub static INTERRUMPIDO: AtomicBool = AtomicBool::new(false);
+
+/// Indica si se pidió interrumpir la carga.
+pub fn interrumpido() -> bool {
+    INTERRUMPIDO.load(Ordering::SeqCst)
+}
+
+/// Error con el que termina la carga cuando se la interrumpe.
+#[derive(Debug, Clone, Copy)]
+pub struct Interrupcion;
+
+impl fmt::Display for Interrupcion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "La carga fue interrumpida.")
+    }
+}
+
+impl std::error::Error for Interrupcion {}
+
+/// Nombre de la tabla que corresponde al tipo `T`.
+fn nombre_tabla<T>() -> &'static str {
+    std::any::type_name::<T>().rsplit("::").next().unwrap()
+}
+
+/// Inserta `fila` salvo que se haya interrumpido la carga, en cuyo caso devuelve [`Interrupcion`]
+/// sin enviar nada a la base de datos. La inserción que ya está en curso al interrumpirse la carga
+/// se termina normalmente.
+///
+/// Si la inserción falla por un error transitorio se reintenta según [`reintentos::REINTENTOS`];
+/// si falla por un error permanente, o se agotan los reintentos, se informa el error y la fila se
+/// cuenta como rechazada.
+///
+/// Con la carga masiva activada, la fila se acumula y se envía después junto con las demás filas
+/// de su tabla (ver [`masiva::acumular`]).
+///
+/// Devuelve si la base de datos aceptó la fila. Con la carga masiva siempre devuelve `true`,
+/// porque la fila todavía no se envió.
+pub async fn insertar<T>(conexion: &Conexion, fila: &T) -> anyhow::Result<bool>
+where
+    T: Insertable + Clone + Send + Sync + 'static,
+{
+    if masiva::carga_masiva_activa().await {
+        masiva::acumular(conexion, fila).await?;
+        return Ok(true);
+    }
+    insertar_fila(conexion, fila).await
+}
+
+/// Inserta `fila` por sí sola, como [`insertar`] sin la carga masiva.
+pub(crate) async fn insertar_fila<T: Insertable>(
+    conexion: &Conexion,
+    fila: &T,
+) -> anyhow::Result<bool> {
+    let tabla = nombre_tabla::<T>();
+    let politica = reintentos::reintentos();
+    let mut intento = 0;
+    loop {
+        if interrumpido() {
+            return Err(Interrupcion.into());
+        }
+        match conexion.insertar(fila).await {
+            Ok(()) => {
+                incrementar_contador(Notificacion::INFO, tabla).await;
+                return Ok(true);
+            }
+            Err(err) if intento < politica.maximo && reintentos::es_transitorio(&err) => {
+                let espera = politica.espera(intento, &mut rand::thread_rng());
+                notificar_carga(
+                    Notificacion::WARN,
+                    &format!(
+                        "{err} (reintento {} de {} en {} ms)",
+                        intento + 1,
+                        politica.maximo,
+                        espera.as_millis()
+                    ),
+                );
+                registrar_reintento(tabla).await;
+                tokio::time::sleep(espera).await;
+                intento += 1;
+            }
+            Err(err) => {
+                notificar_carga(Notificacion::WARN, &err.to_string());
+                incrementar_contador(Notificacion::WARN, tabla).await;
+                return Ok(false);
+            }
+        }
+    }
+}
+
+/// Genera y envía a `destino` los datos generados completamente de manera pseudoaleatoria.
+pub async fn cargar_tabla<T>(muestras: usize, destino: &dyn Destino) -> anyhow::Result<Vec<T>>
 where
-    T: DBData + fake::Dummy<fake::Faker>,
+    T: Insertable + Clone + Send + Sync + 'static + fake::Dummy<fake::Faker>,
 {
     let mut tablas: Vec<T> = Vec::with_capacity(muestras);
     for _ in 1..=muestras {
-        let registro: T = Faker.fake();
-        registro.insertar_en_db(pool).await?;
+        let registro: T = db_tablas::generar();
+        destino.enviar(&registro).await?;
         tablas.push(registro);
     }
 
-    let nombre_tabla = std::any::type_name::<T>().rsplit("::").next().unwrap();
-    notificar_carga(Notificacion::INFO, nombre_tabla);
+    notificar_carga(Notificacion::INFO, nombre_tabla::<T>());
     Ok(tablas)
 }
 
@@ -66,9 +194,27 @@ pub fn notificar_carga(tipo: Notificacion, data: &str) {
 
 pub static CONTADOR: Lazy<Mutex<ContadorGlobal>> = Lazy::new(|| Mutex::new(ContadorGlobal::new()));
 
+/// Resultado de las inserciones en una tabla.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConteoTabla {
+    pub tabla: String,
+    pub cargadas: usize,
+    pub rechazadas: usize,
+    /// Reintentos por errores transitorios.
+    pub reintentos: usize,
+}
+
 pub struct ContadorGlobal {
     total: usize,
     subtotals: HashMap<Notificacion, usize>,
+    /// Conteos de cada tabla, en el orden en que se empezaron a cargar.
+    por_tabla: Vec<ConteoTabla>,
+}
+
+impl Default for ContadorGlobal {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ContadorGlobal {
@@ -76,11 +222,43 @@ impl ContadorGlobal {
         Self {
             total: 0,
             subtotals: HashMap::new(),
+            por_tabla: Vec::new(),
+        }
+    }
+    fn conteo_mut(&mut self, tabla: &str) -> &mut ConteoTabla {
+        let i = match self.por_tabla.iter().position(|c| c.tabla == tabla) {
+            Some(i) => i,
+            None => {
+                self.por_tabla.push(ConteoTabla {
+                    tabla: tabla.to_string(),
+                    ..Default::default()
+                });
+                self.por_tabla.len() - 1
+            }
+        };
+        &mut self.por_tabla[i]
+    }
+
+    pub fn increment(&mut self, notif: Notificacion, tabla: &str) {
+        self.increment_by(notif, tabla, 1);
+    }
+
+    pub fn increment_by(&mut self, notif: Notificacion, tabla: &str, cantidad: usize) {
+        self.total += cantidad;
+        *self.subtotals.entry(notif).or_insert(0) += cantidad;
+        let conteo = self.conteo_mut(tabla);
+        match notif {
+            Notificacion::INFO => conteo.cargadas += cantidad,
+            _ => conteo.rechazadas += cantidad,
         }
     }
-    pub fn increment(&mut self, notif: Notificacion) {
-        self.total += 1;
-        *self.subtotals.entry(notif).or_insert(0) += 1;
+
+    pub fn registrar_reintento(&mut self, tabla: &str) {
+        self.conteo_mut(tabla).reintentos += 1;
+    }
+
+    pub fn get_por_tabla(&self) -> Vec<ConteoTabla> {
+        self.por_tabla.clone()
     }
     pub fn get_total(&self) -> usize {
         self.total
@@ -89,11 +267,38 @@ impl ContadorGlobal {
     pub fn get_subtotals(&self) -> HashMap<Notificacion, usize> {
         self.subtotals.clone()
     }
+
+    fn conteo(&self, tabla: &str) -> ConteoTabla {
+        self.por_tabla
+            .iter()
+            .find(|c| c.tabla == tabla)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Posición en [`TABLAS`] de la última tabla que recibió filas.
+    fn ultima_tabla(&self) -> Option<usize> {
+        TABLAS.iter().rposition(|&tabla| {
+            let conteo = self.conteo(tabla);
+            conteo.cargadas + conteo.rechazadas > 0
+        })
+    }
 }
 
-pub async fn incrementar_contador(category: Notificacion) {
+pub async fn incrementar_contador(category: Notificacion, tabla: &str) {
     let mut counter = CONTADOR.lock().await;
-    counter.increment(category);
+    counter.increment(category, tabla);
+}
+
+/// Cuenta de una vez `cantidad` filas de `tabla`, como las de un lote de la carga masiva.
+pub async fn sumar_al_contador(category: Notificacion, tabla: &str, cantidad: usize) {
+    let mut counter = CONTADOR.lock().await;
+    counter.increment_by(category, tabla, cantidad);
+}
+
+pub async fn registrar_reintento(tabla: &str) {
+    let mut counter = CONTADOR.lock().await;
+    counter.registrar_reintento(tabla);
 }
 
 pub async fn generar_reporte() {
@@ -125,4 +330,53 @@ pub async fn generar_reporte() {
             .bold()
     );
     eprintln!("{msg}");
+
+    let reintentos: Vec<&ConteoTabla> = counter
+        .por_tabla
+        .iter()
+        .filter(|c| c.reintentos > 0)
+        .collect();
+    if !reintentos.is_empty() {
+        let total: usize = reintentos.iter().map(|c| c.reintentos).sum();
+        eprintln!("    - {total:<6} Reintentos por errores transitorios:");
+        for c in reintentos {
+            eprintln!("        - {:<34} {:>6}", c.tabla, c.reintentos);
+        }
+    }
+}
+
+/// Tabla en la que se interrumpió la carga, es decir, la última que recibió filas.
+pub async fn tabla_interrumpida() -> Option<&'static str> {
+    CONTADOR.lock().await.ultima_tabla().map(|u| TABLAS[u])
+}
+
+/// Muestra, para cada tabla, las filas cargadas y rechazadas hasta el momento y si la tabla se
+/// terminó de cargar, se interrumpió o no se llegó a cargar. Devuelve la tabla interrumpida.
+///
+/// Las tablas se cargan en el orden de [`TABLAS`], por lo que todas las anteriores a la última
+/// que recibió filas están completas.
+pub async fn generar_reporte_por_tabla() -> Option<&'static str> {
+    let counter = CONTADOR.lock().await;
+    let conteo = |tabla: &str| counter.conteo(tabla);
+    let ultima = counter.ultima_tabla();
+
+    eprintln!("\nTablas:");
+    for (i, &tabla) in TABLAS.iter().enumerate() {
+        let ConteoTabla {
+            cargadas,
+            rechazadas,
+            reintentos,
+            ..
+        } = conteo(tabla);
+        let estado = match ultima {
+            Some(u) if i < u => "COMPLETA".bright_green().bold(),
+            Some(u) if i == u => "INTERRUMPIDA".bright_yellow().bold(),
+            _ => "PENDIENTE".bright_red().bold(),
+        };
+        eprintln!(
+            "    - {estado:<12} {tabla:<34} {cargadas:>8} cargadas, {rechazadas:>6} rechazadas, \
+            {reintentos:>4} reintentos."
+        );
+    }
+    ultima.map(|u| TABLAS[u])
 }
@@ -1,58 +1,14 @@
 // Autor: Acosta Quintana, Lautaro
 
-use carga_datos::{datasets::*, db_tablas::*, Notificacion::INFO, *};
-use clap::Parser;
-use dbdata::DBData;
-use rand::{
-    rngs::StdRng,
-    seq::{IteratorRandom, SliceRandom},
-    Rng, SeedableRng,
+use carga_datos::{
+    auditoria::*, cardinalidad::*, carga::OpcionesCarga, citas::*, conexion::*, datasets::*,
+    destino::*, distribucion::*, estadisticas::*, generador::Generador, guardia::*,
+    masiva::ConexionInfile, plan::*, reintentos::Reintentos, *,
 };
-use std::error::Error;
-
-/* Orden de carga hasta ahora:
-- Primero aquellas tablas que no tienen FKs.
-    01. Direcciones
-    02. Titulos
-    03. Publicaciones
-    04. Reuniones
-    05. Percepciones
-    06. Seguros
-    07. ObrasSociales
-    08. Idiomas
-
-- Segundo, aquellas tablas que contienen FKs.
-
-    09. Empleadores
-    10. Instituciones
-    11. CursosConferencias
-    12. ActividadesExtensionUniversitaria
-    13. ActividadesInvestigacion
-    14. Profesores
-    15. Contactos
-    16. DependenciasEmpresas
-    17. Familiares
-    18. DocObraSocial
-    19. DeclaracionesJuradas
-    20. DeclaracionesDeCargo
-    21. AntecedentesProfesionales
-    22. AntecedentesDocentes
-    23. Horarios
-    24. AtendioA
-    25. SeDaIdiomas
-    26. ConoceIdiomas
-    27. Beneficia
-    28. PoseeTitulo
-    29. SeDaTitulo
-    30. RealizaInvestigacion
-    31. RealizoActividad
-    32. ReferenciasBibliograficas
-    33. Publico
-    34. ParticipoEnReunion
-    35. PercibeEn
-    36. ResideEn
-    37. AseguraA
-*/
+use clap::{Parser, Subcommand, ValueEnum};
+use colored::Colorize;
+use dbdata::Conexion;
+use std::{error::Error, path::PathBuf, process::ExitCode, sync::Arc, time::Duration};
 
 #[derive(Parser, Debug)]
 #[command(version = "0.1.1")]
@@ -60,343 +16,601 @@ use std::error::Error;
 /// Base de Datos en la Universidad Nacional Regional Resistencia.
 struct Args {
     /// Cantidad de registros a generar en cada tabla.
-    #[arg(short, long, default_value_t = 1000)]
+    #[arg(short, long, default_value_t = 1000, global = true)]
     cantidad: usize,
+
+    /// Directorio desde donde leer los datasets en lugar de usar los incluidos en el binario.
+    #[arg(long, global = true)]
+    datasets: Option<PathBuf>,
+
+    /// Distribución con la que se reparten los hijos de una relación entre sus padres, con la
+    /// forma '<Relacion>=<distribución>'. Las distribuciones posibles son 'uniforme',
+    /// 'zipf:<exponente>', 'normal:<media>,<desvío>' (hijos por padre) y 'fija:<min>..<max>'
+    /// (hijos por padre). Se puede indicar varias veces.
+    #[arg(long = "distribucion", global = true, value_parser = parsear_asignacion)]
+    distribuciones: Vec<(String, Distribucion)>,
+
+    /// Cantidad mínima y máxima de hijos que debe tener cada padre de una relación, con la forma
+    /// '<Padre>-><Hijo>:<min>..<max>' (el máximo se puede omitir). Por defecto cada profesor
+    /// tiene al menos un contacto, una declaración jurada y un domicilio. Se puede indicar varias
+    /// veces.
+    #[arg(long = "cardinalidad", global = true, value_parser = parsear_cardinalidad)]
+    cardinalidades: Vec<Cardinalidad>,
+
+    /// Cantidad mínima y máxima de publicaciones que cita cada publicación, con la forma
+    /// '<min>..<max>'. Cada publicación sólo cita publicaciones de años anteriores.
+    #[arg(long, global = true, value_parser = parsear_referencias, default_value = "0..4")]
+    referencias: (usize, usize),
+
+    /// Peso de una publicación sin citas al elegir las que cita otra publicación; cuanto menor,
+    /// más se concentran las citas en las publicaciones ya citadas.
+    #[arg(long, global = true, value_parser = parsear_atraccion, default_value_t = 1.0)]
+    atraccion_citas: f64,
+
+    /// Motor de la base de datos indicada con '--database-url'. Los comandos 'auditar' y
+    /// 'estadisticas' sólo están disponibles para MySQL.
+    #[arg(long, global = true, value_enum, default_value_t = Motor::MySql)]
+    motor: Motor,
+
+    /// URL de la base de datos. Si no se indica se usa la variable 'DATABASE_URL', que se puede
+    /// declarar en el archivo .env.
+    #[arg(long, global = true)]
+    database_url: Option<String>,
+
+    /// Lee las variables de ambiente de '.env.test' o '.env.dev' en lugar de '.env'.
+    #[arg(long, global = true, value_enum)]
+    perfil: Option<Perfil>,
+
+    /// Cantidad máxima de conexiones abiertas con la base de datos.
+    #[arg(long, global = true, default_value_t = 10)]
+    max_conexiones: u32,
+
+    /// Segundos a esperar para establecer la conexión con la base de datos.
+    #[arg(long, global = true, default_value_t = 10)]
+    timeout_conexion: u64,
+
+    /// Segundos a esperar para obtener una conexión libre del pool.
+    #[arg(long, global = true, default_value_t = 4)]
+    timeout_adquisicion: u64,
+
+    /// Cifrado de la conexión. Si no se indica se usa el de la URL.
+    #[arg(long, global = true, value_enum)]
+    tls: Option<ModoTls>,
+
+    /// Socket de Unix con el cual conectarse en lugar de usar TCP.
+    #[arg(long, global = true)]
+    socket: Option<PathBuf>,
+
+    /// Cantidad máxima de reintentos de una fila cuya inserción falla por un error transitorio,
+    /// como una conexión perdida, un deadlock o un tiempo de espera agotado.
+    #[arg(long, default_value_t = Reintentos::POR_DEFECTO.maximo)]
+    reintentos: u32,
+
+    /// Milisegundos a esperar antes del primer reintento. La espera se duplica en cada reintento.
+    #[arg(long, default_value_t = 100)]
+    espera_reintento: u64,
+
+    /// Milisegundos a esperar como máximo entre dos reintentos.
+    #[arg(long, default_value_t = 5000)]
+    espera_maxima_reintento: u64,
+
+    /// Patrón del nombre de una base de datos en la que se puede cargar sin '--forzar', donde '*'
+    /// representa cualquier secuencia de caracteres. Se puede indicar varias veces. Por defecto
+    /// se permiten 'tpi*', '*test*', '*prueba*', '*dev*' y ':memory:'.
+    #[arg(long = "bd-permitida")]
+    bases_permitidas: Vec<String>,
+
+    /// Carga los datos aunque la base de datos no esté vacía o su nombre no esté permitido.
+    #[arg(long)]
+    forzar: bool,
+
+    /// Se conecta con la base de datos e informa qué se haría, sin escribir en ella.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Acumula las filas de cada tabla y las envía juntas: con LOAD DATA LOCAL INFILE en MySQL,
+    /// si se compiló con la feature 'carga-masiva' y el servidor lo permite, o si no con INSERT
+    /// de muchas filas.
+    #[arg(long)]
+    masiva: bool,
+
+    /// Además de cargar la base de datos, escribe las filas generadas en un script SQL para el
+    /// motor indicado con '--motor'.
+    #[arg(long)]
+    sql: Option<PathBuf>,
+
+    /// Además de cargar la base de datos, escribe las filas generadas de cada tabla en un archivo
+    /// CSV '<Tabla>.csv' de este directorio.
+    #[arg(long)]
+    csv: Option<PathBuf>,
+
+    /// Semilla con la que se generan los datos. Con la misma semilla, las mismas opciones y los
+    /// mismos datasets se generan los mismos datos; sin ella se elige una al azar.
+    #[arg(long)]
+    semilla: Option<u64>,
+
+    /// Proporción, entre 0 y 1, de obras sociales, aseguradoras y cajas previsionales inventadas
+    /// en lugar de tomadas de los datasets. Con 0 sólo se inventan las que faltan cuando se
+    /// agotan las de los datasets.
+    #[arg(long, default_value_t = 0.0)]
+    ficticias: f64,
+
+    #[command(subcommand)]
+    comando: Option<Comando>,
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
-    let pool = conectar_con_bd().await?;
-    sqlx::migrate!("./migrations").run(&pool).await?;
-
-    let muestras = Args::parse().cantidad;
-    let mut nombre_universidades = cargar_de_csv("./datasets/universidades.csv")?;
-    let provincias = cargar_provincias("./datasets/provincia_localidad_calles.csv")?;
-    let idiomas: Vec<Idiomas> = cargar_de_csv("./datasets/idiomas.csv")?
-        .into_iter()
-        .map(|x| Idiomas::new(&x))
-        .collect();
-    let mut rng = StdRng::from_entropy();
-
-    // Primero aquellas tablas que no tienen FK.
-    let mut direcciones = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let provincia = provincias.choose(&mut rng).unwrap();
-        let localidad = provincia.localidades.choose(&mut rng).unwrap();
-        let calle = localidad.calles.choose(&mut rng).unwrap();
-        let fila = Direcciones::new(&provincia.nombre, &localidad.nombre, calle);
-        fila.insertar_en_db(&pool).await?;
-        direcciones.push(fila);
+impl Args {
+    fn config_conexion(&self) -> ConfigConexion {
+        ConfigConexion {
+            url: self.database_url.clone(),
+            perfil: self.perfil,
+            max_conexiones: self.max_conexiones,
+            timeout_conexion: Duration::from_secs(self.timeout_conexion),
+            timeout_adquisicion: Duration::from_secs(self.timeout_adquisicion),
+            tls: self.tls,
+            socket: self.socket.clone(),
+            solo_lectura: self.dry_run,
+        }
     }
 
-    notificar_carga(INFO, "Direcciones");
-
-    let titulos = cargar_tabla::<Titulos>(muestras, &pool).await?;
-    let publicaciones = cargar_tabla::<Publicaciones>(muestras, &pool).await?;
-    let reuniones = cargar_tabla::<ReunionesCientificas>(muestras, &pool).await?;
-    let percepciones = cargar_tabla::<Percepciones>(muestras, &pool).await?;
-    let seguros = cargar_tabla::<Seguros>(muestras, &pool).await?;
-
-    let mut obras_sociales = cargar_tabla::<ObrasSociales>(muestras, &pool).await?;
-    let dasuten = ObrasSociales::new("D.A.S.U.Te.N", rng.gen());
-    dasuten.insertar_en_db(&pool).await?;
-    obras_sociales.push(dasuten);
+    fn bases_permitidas(&self) -> Vec<String> {
+        if self.bases_permitidas.is_empty() {
+            BASES_PERMITIDAS.iter().map(|p| p.to_string()).collect()
+        } else {
+            self.bases_permitidas.clone()
+        }
+    }
 
-    let row_count: i64 = sqlx::query_scalar("select count(*) FROM Idiomas")
-        .fetch_one(&pool)
-        .await?;
+    fn opciones_carga(&self) -> OpcionesCarga {
+        let mut opciones = OpcionesCarga {
+            muestras: self.cantidad,
+            semilla: self.semilla,
+            ficticias: self.ficticias,
+            citas: Citas {
+                min: self.referencias.0,
+                max: self.referencias.1,
+                atraccion: self.atraccion_citas,
+            },
+            ..Default::default()
+        };
+        for (relacion, distribucion) in &self.distribuciones {
+            opciones.distribuciones.configurar(relacion, *distribucion);
+        }
+        for cardinalidad in &self.cardinalidades {
+            opciones.cardinalidades.configurar(*cardinalidad);
+        }
+        opciones
+    }
 
-    if row_count == 0 {
-        for i in &idiomas {
-            i.insertar_en_db(&pool).await?;
+    fn fuente_datasets(&self) -> FuenteDatasets {
+        FuenteDatasets {
+            directorio: self.datasets.clone(),
         }
     }
+}
 
-    notificar_carga(INFO, "Idiomas");
-    //cargar_idiomas(&idiomas, &pool).await?;
+#[derive(Subcommand, Debug)]
+enum Comando {
+    /// Operaciones sobre los datasets utilizados para generar los datos.
+    Datasets {
+        #[command(subcommand)]
+        accion: AccionDatasets,
+    },
+    /// Revisa una base de datos ya cargada en busca de filas huérfanas, valores que no cumplen
+    /// los CHECK, violaciones a las reglas de negocio y tablas con menos filas que las pedidas
+    /// con '--cantidad'. Termina con un código de error si encuentra algún problema.
+    Auditar {
+        /// Formato del informe.
+        #[arg(long, value_enum, default_value_t = Formato::Tabla)]
+        formato: Formato,
+    },
+    /// Informa, para cada tabla de una base de datos ya cargada, la cantidad de filas y, para
+    /// cada columna, la proporción de nulos, la cantidad de valores distintos, el mínimo y el
+    /// máximo de las fechas y números y los valores más frecuentes de los enums.
+    Estadisticas {
+        /// Formato del informe.
+        #[arg(long, value_enum, default_value_t = Formato::Tabla)]
+        formato: Formato,
+
+        /// Cantidad de valores más frecuentes a informar para cada enum.
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+    },
+    /// Muestra, sin conectarse con la base de datos, las tablas en el orden en que se cargan, la
+    /// cantidad de filas que se generarían en cada una según '--cantidad', '--distribucion' y
+    /// '--cardinalidad', el tamaño estimado a partir de los tipos de las columnas y los datasets
+    /// que se utilizan.
+    Plan {
+        /// Formato del informe.
+        #[arg(long, value_enum, default_value_t = Formato::Tabla)]
+        formato: Formato,
+    },
+}
 
-    let mut empleadores = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let direccion = direcciones.choose(&mut rng).unwrap();
-        let fila = Empleadores::new(direccion);
-        fila.insertar_en_db(&pool).await?;
-        empleadores.push(fila);
-    }
-    notificar_carga(INFO, "Empleadores");
-
-    let mut instituciones = Vec::with_capacity(muestras);
-    nombre_universidades.shuffle(&mut rng);
-    for nombre in nombre_universidades
-        .iter()
-        .choose_multiple(&mut rng, muestras)
-    {
-        let direccion = direcciones.choose(&mut rng).unwrap();
-        let fila = Instituciones::new(direccion, nombre);
-        fila.insertar_en_db(&pool).await?;
-        instituciones.push(fila);
-    }
-    notificar_carga(INFO, "Instituciones");
-
-    let mut cur_conf = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let institucion = instituciones.choose(&mut rng).unwrap();
-        let fila = CursosConferencias::new(institucion);
-        fila.insertar_en_db(&pool).await?;
-        cur_conf.push(fila);
-    }
-    notificar_carga(INFO, "CursosConferencias");
-
-    //FIXME: Tiene sentido cargar tantas actividades como muestras?
-    let mut act_uni = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let institucion = instituciones.choose(&mut rng).unwrap();
-        let fila = ActividadesExtensionUniversitaria::new(institucion);
-        fila.insertar_en_db(&pool).await?;
-        act_uni.push(fila);
-    }
-    notificar_carga(INFO, "ActividadesExtensionUniversitaria");
-
-    //FIXME: Tiene sentido cargar tantas actividades como muestras?
-    let mut act_inv = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let institucion = instituciones.choose(&mut rng).unwrap();
-        let fila = ActividadesInvestigacion::new(institucion);
-        fila.insertar_en_db(&pool).await?;
-        act_inv.push(fila);
-    }
-    notificar_carga(INFO, "ActividadesInvestigacion");
-
-    let mut profesores = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let empleador = empleadores.choose(&mut rng).unwrap();
-        let fila = Profesores::new(empleador);
-        fila.insertar_en_db(&pool).await?;
-        profesores.push(fila);
-    }
-    notificar_carga(INFO, "Profesores");
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Formato {
+    Tabla,
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+enum AccionDatasets {
+    /// Informa los archivos faltantes, problemas en los encabezados y la cantidad de filas de
+    /// cada dataset.
+    Verificar,
+}
 
-    let mut contactos = Vec::with_capacity(muestras);
-    for prof in &profesores {
-        let fila = Contactos::new(prof);
-        fila.insertar_en_db(&pool).await?;
-        contactos.push(fila)
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    let resultado = match args.comando {
+        Some(Comando::Datasets {
+            accion: AccionDatasets::Verificar,
+        }) => verificar_datasets(&args.fuente_datasets()),
+        Some(Comando::Auditar { formato }) => auditar_bd(&args, formato).await,
+        Some(Comando::Estadisticas { formato, top }) => {
+            informar_estadisticas(&args, formato, top).await
+        }
+        Some(Comando::Plan { formato }) => informar_plan(&args, formato),
+        None => cargar(&args).await,
+    };
+
+    match resultado {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            notificar_carga(Notificacion::ERROR, &err.to_string());
+            ExitCode::FAILURE
+        }
     }
-    notificar_carga(INFO, "Contactos");
-
-    let mut dep_emp = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let obra = obras_sociales.choose(&mut rng).unwrap();
-        let direccion = direcciones.choose(&mut rng).unwrap();
-        let profesor = profesores.choose(&mut rng).unwrap();
-        let fila = DependenciasEmpresas::new(profesor, direccion, obra);
-        fila.insertar_en_db(&pool).await?;
-        dep_emp.push(fila);
+}
+
+/// Genera y carga los datos de todas las tablas.
+async fn cargar(args: &Args) -> Result<(), Box<dyn Error>> {
+    let opciones = args.opciones_carga();
+    let config = args.config_conexion();
+    let pool = conectar(args.motor, &config).await?;
+    if args.dry_run {
+        Datasets::cargar(&args.fuente_datasets())?;
+        let inspeccion = inspeccionar(&pool).await?;
+        let problemas = inspeccion.problemas(&args.bases_permitidas());
+        return informar_dry_run(&inspeccion, &problemas, &opciones, args.forzar);
     }
-    notificar_carga(INFO, "DependenciasEmpresas");
-
-    let mut familiares = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let direccion = direcciones.choose(&mut rng).unwrap();
-        let profesor = profesores.choose(&mut rng).unwrap();
-        let fila = Familiares::new(direccion, profesor);
-        fila.insertar_en_db(&pool).await?;
-        familiares.push(fila);
+
+    let mut generador = Generador::new(pool.clone())
+        .opciones(opciones.clone())
+        .datasets(args.fuente_datasets())
+        .reintentos(Reintentos {
+            maximo: args.reintentos,
+            espera_inicial: Duration::from_millis(args.espera_reintento),
+            espera_maxima: Duration::from_millis(args.espera_maxima_reintento),
+        })
+        .masiva(args.masiva)
+        .bases_permitidas(args.bases_permitidas())
+        .forzar(args.forzar);
+    if args.masiva && matches!(pool, Conexion::MySql(_)) {
+        match ConexionInfile::new(&config)? {
+            Some(infile) => generador = generador.infile(infile),
+            None => notificar_carga(
+                Notificacion::WARN,
+                "LOAD DATA LOCAL INFILE no puede cifrar la conexión como pide '--tls', por lo que \
+                la carga masiva usa INSERT de muchas filas.",
+            ),
+        }
     }
-    notificar_carga(INFO, "Familiares");
-
-    let mut doc_obras = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let obra = obras_sociales.choose(&mut rng).unwrap();
-        let profesor = profesores.choose(&mut rng).unwrap();
-        let fila = DocObraSocial::new(profesor, obra);
-        fila.insertar_en_db(&pool).await?;
-        doc_obras.push(fila);
+    if let Some(ruta) = &args.sql {
+        generador = generador.destino(Arc::new(ScriptSql::crear(ruta, args.motor)?));
     }
-    notificar_carga(INFO, "DocObraSocial");
-
-    let mut dec_jur = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let profesor = profesores.choose(&mut rng).unwrap();
-        let fila = DeclaracionesJuradas::new(profesor);
-        fila.insertar_en_db(&pool).await?;
-        dec_jur.push(fila);
+    if let Some(directorio) = &args.csv {
+        generador = generador.destino(Arc::new(DirectorioCsv::crear(directorio)?));
     }
-    notificar_carga(INFO, "DeclaracionesJuradas");
-
-    let mut dec_car = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let dep = dep_emp.choose(&mut rng).unwrap();
-        let fila = DeclaracionesDeCargo::new(dep);
-        fila.insertar_en_db(&pool).await?;
-        dec_car.push(fila);
+    escuchar_interrupciones();
+    let generacion = match generador.ejecutar().await {
+        Ok(generacion) => generacion,
+        Err(err) if err.is::<BaseRechazada>() => {
+            for problema in &err.downcast_ref::<BaseRechazada>().unwrap().problemas {
+                notificar_carga(Notificacion::ERROR, problema);
+            }
+            return Err(
+                "No se cargó la base de datos. Use '--forzar' para cargarla de todos modos.".into(),
+            );
+        }
+        Err(err) if err.is::<Interrupcion>() => {
+            generar_reporte().await;
+            return Err(match generar_reporte_por_tabla().await {
+                Some(tabla) => format!(
+                    "La carga fue interrumpida en la tabla {tabla}; las anteriores están completas."
+                ),
+                None => "La carga fue interrumpida antes de cargar alguna tabla.".to_string(),
+            }
+            .into());
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    generar_reporte().await;
+    eprintln!(
+        "\nSemilla: {} (use '--semilla {}' para volver a generar los mismos datos)",
+        generacion.semilla, generacion.semilla
+    );
+    informar_cardinalidades(&opciones.cardinalidades, &pool).await
+}
+
+/// Al recibir Ctrl-C la carga deja de insertar filas, termina la inserción en curso e informa lo
+/// cargado hasta el momento. Un segundo Ctrl-C termina el programa inmediatamente.
+fn escuchar_interrupciones() {
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+        INTERRUMPIDO.store(true, std::sync::atomic::Ordering::SeqCst);
+        notificar_carga(
+            Notificacion::WARN,
+            "Se recibió Ctrl-C: se detiene la carga. Presione Ctrl-C otra vez para salir sin \
+            esperar el informe.",
+        );
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130);
+        }
+    });
+}
+
+/// Informa por STDOUT el estado de la base de datos y lo que se haría al cargarla.
+fn informar_dry_run(
+    inspeccion: &Inspeccion,
+    problemas: &[String],
+    opciones: &OpcionesCarga,
+    forzar: bool,
+) -> Result<(), Box<dyn Error>> {
+    println!("Base de datos:            {}", inspeccion.nombre);
+    println!(
+        "Cargada por el generador: {}",
+        if inspeccion.marcada { "sí" } else { "no" }
+    );
+    println!(
+        "Migraciones pendientes:   {}",
+        inspeccion.migraciones_pendientes
+    );
+    if let Some(carga) = &inspeccion.ultima_carga {
+        println!("Última carga:             {carga}");
     }
-    notificar_carga(INFO, "DeclaracionesDeCargo");
-
-    let mut ant_pro = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let profesor = profesores.choose(&mut rng).unwrap();
-        let declaracion = dec_car.choose(&mut rng).unwrap();
-        let fila = AntecedentesProfesionales::new(profesor, declaracion);
-        fila.insertar_en_db(&pool).await?;
-        ant_pro.push(fila)
+    println!("Tablas con filas:         {}", inspeccion.filas.len());
+    for (tabla, filas) in &inspeccion.filas {
+        println!("    - {tabla:<34} {filas:>8}");
     }
-    notificar_carga(INFO, "AntecedentesProfesionales");
-
-    let mut ant_doc = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let institucion = instituciones.choose(&mut rng).unwrap();
-        let profesor = profesores.choose(&mut rng).unwrap();
-        let declaracion = dec_car.choose(&mut rng).unwrap();
-        let fila = AntecedentesDocentes::new(profesor, institucion, declaracion);
-        fila.insertar_en_db(&pool).await?;
-        ant_doc.push(fila);
+    if !inspeccion.ajenas.is_empty() {
+        println!("Tablas ajenas al esquema: {}", inspeccion.ajenas.join(", "));
     }
-    notificar_carga(INFO, "AntecedentesDocentes");
-
-    let mut horarios = Vec::with_capacity(muestras);
-    for _ in 1..=muestras {
-        let declaraciones = dec_car.choose(&mut rng).unwrap();
-        let fila = Horarios::new(declaraciones);
-
-        fila.insertar_en_db(&pool).await?;
-        horarios.push(fila);
+    println!(
+        "\nSe generarían {} registros por cada una de las {} tablas.",
+        opciones.muestras,
+        esquema::TABLAS.len()
+    );
+    for cardinalidad in opciones.cardinalidades.iter() {
+        println!("    - {cardinalidad}");
     }
-    notificar_carga(INFO, "Horarios");
 
-    for prof in &profesores {
-        let curso = cur_conf.choose(&mut rng).unwrap();
-        AtendioA::new(curso, prof).insertar_en_db(&pool).await?;
+    if problemas.is_empty() {
+        println!("\n{} La carga se realizaría.", "OK".bright_green().bold());
+        return Ok(());
     }
-    notificar_carga(INFO, "AtendioA");
-    //cargar_atendio_a(&cur_conf, &profesores, &pool)
-
-    for prof in &profesores {
-        ConoceIdioma::new(&Idiomas::new("Español"), prof)
-            .insertar_en_db(&pool)
-            .await?;
-        for _ in 1..=rng.gen_range(1..3) {
-            let idioma = idiomas.choose(&mut rng).unwrap();
-            ConoceIdioma::new(idioma, prof)
-                .insertar_en_db(&pool)
-                .await?;
-        }
+    println!("\nProblemas:");
+    for problema in problemas {
+        println!("    - {problema}");
     }
-    notificar_carga(INFO, "ConoceIdiomas");
-    //cargar_conoce_idiomas(&idiomas, &profesores, &pool),
-
-    for inst in &instituciones {
-        for _ in 1..=rng.gen_range(1..3) {
-            let idioma = idiomas.choose(&mut rng).unwrap();
-            SeDaIdioma::new(idioma, inst).insertar_en_db(&pool).await?;
-        }
+    if forzar {
+        println!(
+            "\n{} La carga se realizaría por '--forzar'.",
+            "WARN".bright_yellow().bold()
+        );
+        Ok(())
+    } else {
+        Err("La carga se rechazaría. Use '--forzar' para cargarla de todos modos.".into())
     }
-    notificar_carga(INFO, "SeDaIdiomas");
-    //cargar_se_da_idiomas(&idiomas, &instituciones, &pool),
+}
 
-    for _ in 1..=rng.gen_range((muestras / 2)..muestras) {
-        let obra = obras_sociales.choose(&mut rng).unwrap();
-        let familiar = familiares.choose(&mut rng).unwrap();
-        Beneficia::new(obra, familiar).insertar_en_db(&pool).await?;
-    }
-    notificar_carga(INFO, "Beneficia");
-    //cargar_beneficia(&obras_sociales, &familiares, muestras, &pool),
-
-    {
-        let (terciarios, otros): (Vec<Titulos>, Vec<Titulos>) = titulos
-            .iter()
-            .cloned()
-            .partition(|x| x.nivel == "Terciario");
-
-        for prof in &profesores {
-            let t = terciarios
-                .choose(&mut rng)
-                .expect("No hay titulos terciarios en la tabla Titulos.");
-            PoseeTitulo::new(t, prof).insertar_en_db(&pool).await?;
-        }
-        for _ in 0..rng.gen_range(0..muestras) {
-            let t = otros
-                .choose(&mut rng)
-                .expect("No hay titulos no terciarios en la tabla Titulos.");
-            let prof = profesores.choose(&mut rng).unwrap();
-            PoseeTitulo::new(t, prof).insertar_en_db(&pool).await?;
-        }
-        notificar_carga(INFO, "PoseeTitulo");
+/// Comprueba las cardinalidades contra los datos cargados e informa las que no se cumplen.
+async fn informar_cardinalidades(
+    cardinalidades: &Cardinalidades,
+    pool: &Conexion,
+) -> Result<(), Box<dyn Error>> {
+    eprintln!("\nCardinalidades:");
+    for informe in comprobar_cardinalidades(cardinalidades, pool).await? {
+        let estado = if informe.se_cumple() {
+            "OK".bright_green().bold()
+        } else {
+            "ERROR".bright_red().bold()
+        };
+        eprintln!(
+            "    - {:<5} {:<45} {:>6} padres por debajo del mínimo, {:>6} por encima del máximo.",
+            estado,
+            informe.cardinalidad.to_string(),
+            informe.por_debajo,
+            informe.por_encima
+        );
     }
-    //cargar_posee_titulo(&titulos, &profesores, muestras, &pool),
+    Ok(())
+}
 
-    for inst in &instituciones {
-        for _ in 1..=rng.gen_range(1..5) {
-            let titulo = titulos.choose(&mut rng).unwrap();
-            SeDaTitulo::new(titulo, inst).insertar_en_db(&pool).await?;
+/// Verifica cada uno de los datasets e informa el resultado por STDOUT.
+fn verificar_datasets(fuente: &FuenteDatasets) -> Result<(), Box<dyn Error>> {
+    let mut errores = 0;
+    for dataset in DATASETS {
+        let verificacion = dataset.verificar(fuente);
+        let estado = if verificacion.es_valido() {
+            "OK".bright_green().bold()
+        } else {
+            errores += 1;
+            "ERROR".bright_red().bold()
+        };
+        println!(
+            "{:<5} {:<60} {:>8} filas",
+            estado, verificacion.origen, verificacion.filas
+        );
+        for problema in &verificacion.problemas {
+            println!("      - {problema}");
         }
     }
-    notificar_carga(INFO, "SeDaTitulo");
-    //cargar_se_da_titulo(&titulos, &instituciones, &pool),
 
-    for _ in 1..=rng.gen_range((muestras / 2)..muestras) {
-        let act = act_inv.choose(&mut rng).unwrap();
-        let prof = profesores.choose(&mut rng).unwrap();
-        RealizaInves::new(act, prof).insertar_en_db(&pool).await?;
+    if errores > 0 {
+        return Err(format!("{errores} dataset(s) con problemas.").into());
     }
-    notificar_carga(INFO, "RealizaInvestigacion");
-    //cargar_realiza_investigacion(&act_inv, &profesores, muestras, &pool),
+    Ok(())
+}
 
-    for _ in 1..=rng.gen_range((muestras / 2)..muestras) {
-        let act = act_uni.choose(&mut rng).unwrap();
-        let prof = profesores.choose(&mut rng).unwrap();
-        RealizoAct::new(act, prof).insertar_en_db(&pool).await?;
-    }
-    notificar_carga(INFO, "RealizoActividad");
-    //cargar_realizo_actividad(&act_uni, &profesores, muestras, &pool),
-
-    for _ in 1..rng.gen_range(1..publicaciones.len()) {
-        let citador = publicaciones.choose(&mut rng).unwrap();
-        let fuente = publicaciones.choose(&mut rng).unwrap();
-        ReferenciaBibliografica::new(fuente, citador)
-            .insertar_en_db(&pool)
-            .await?;
+/// Audita la base de datos e informa el resultado por STDOUT.
+async fn auditar_bd(args: &Args, formato: Formato) -> Result<(), Box<dyn Error>> {
+    let fuente = args.fuente_datasets();
+    let universidades = cargar_nombres(&UNIVERSIDADES, &fuente)?.len();
+    let titulos = TITULOS.leer::<Titulo>(&fuente)?.len();
+    let pool = conectar_con_bd(&args.config_conexion()).await?;
+    let auditoria = auditar(&pool, args.cantidad, universidades, titulos).await?;
+
+    match formato {
+        Formato::Json => println!("{}", serde_json::to_string_pretty(&auditoria)?),
+        Formato::Tabla => {
+            let estado = |valido: bool| {
+                if valido {
+                    "OK".bright_green().bold()
+                } else {
+                    "ERROR".bright_red().bold()
+                }
+            };
+            println!(
+                "{:<5} {:<15} {:<34} {:<70} {:>8}",
+                "", "Categoría", "Tabla", "Control", "Filas"
+            );
+            for c in &auditoria.controles {
+                println!(
+                    "{:<5} {:<15} {:<34} {:<70} {:>8}",
+                    estado(c.es_valido()),
+                    format!("{:?}", c.categoria),
+                    c.tabla,
+                    c.descripcion,
+                    c.filas
+                );
+            }
+            println!(
+                "\n{:<5} {:<34} {:>8} {:>10}",
+                "", "Tabla", "Filas", "Esperadas"
+            );
+            for c in &auditoria.conteos {
+                println!(
+                    "{:<5} {:<34} {:>8} {:>10}",
+                    estado(c.es_valido()),
+                    c.tabla,
+                    c.filas,
+                    c.esperadas.map_or("-".to_string(), |e| e.to_string())
+                );
+            }
+        }
     }
-    notificar_carga(INFO, "ReferenciasBibliograficas");
-    //cargar_referencias_bibliograficas(&publicaciones, &pool),
 
-    for p in &publicaciones {
-        let profesor = profesores.choose(&mut rng).unwrap();
-        Publico::new(p, profesor).insertar_en_db(&pool).await?;
+    match auditoria.fallas() {
+        0 => Ok(()),
+        n => Err(format!("La auditoría encontró {n} problema(s).").into()),
     }
-    notificar_carga(INFO, "Publico");
-    //cargar_publico(&publicaciones, &profesores, &pool),
-
-    for r in &reuniones {
-        let profesor = profesores.choose(&mut rng).unwrap();
-        ParticipoEnReunion::new(r, profesor)
-            .insertar_en_db(&pool)
-            .await?;
-    }
-    notificar_carga(INFO, "ParticipoEnReunion");
-    //cargar_participo_en_reunion(&reuniones, &profesores, &pool),
+}
 
-    for p in &percepciones {
-        let profesor = profesores.choose(&mut rng).unwrap();
-        PercibeEn::new(p, profesor).insertar_en_db(&pool).await?;
+/// Calcula las estadísticas de la base de datos y las informa por STDOUT.
+async fn informar_estadisticas(
+    args: &Args,
+    formato: Formato,
+    top: usize,
+) -> Result<(), Box<dyn Error>> {
+    let pool = conectar_con_bd(&args.config_conexion()).await?;
+    let tablas = estadisticas(&pool, top).await?;
+
+    match formato {
+        Formato::Json => println!("{}", serde_json::to_string_pretty(&tablas)?),
+        Formato::Tabla => {
+            for tabla in &tablas {
+                println!("{} ({} filas)", tabla.tabla.bold(), tabla.filas);
+                println!(
+                    "    {:<30} {:<10} {:>8} {:>10} {:<22} {:<22}",
+                    "Columna", "Tipo", "Nulos", "Distintos", "Mínimo", "Máximo"
+                );
+                for c in &tabla.columnas {
+                    println!(
+                        "    {:<30} {:<10} {:>8} {:>10} {:<22} {:<22}",
+                        c.nombre,
+                        c.tipo,
+                        c.proporcion_nulos
+                            .map_or("-".to_string(), |p| format!("{:.1}%", p * 100.0)),
+                        c.distintos,
+                        c.minimo.as_deref().unwrap_or("-"),
+                        c.maximo.as_deref().unwrap_or("-"),
+                    );
+                    for (valor, cantidad) in &c.mas_frecuentes {
+                        println!("        - {valor:<26} {cantidad:>8}");
+                    }
+                }
+                println!();
+            }
+        }
     }
-    notificar_carga(INFO, "PercibeEn");
-    //cargar_percibe_en(&percepciones, &profesores, &pool),
+    Ok(())
+}
 
-    for p in &profesores {
-        let dir = direcciones.choose(&mut rng).unwrap();
-        ResideEn::new(p, dir).insertar_en_db(&pool).await?;
+/// Muestra un tamaño en bytes con la unidad más conveniente.
+fn tamano_legible(bytes: usize) -> String {
+    let unidades = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut tamano = bytes as f64;
+    let mut unidad = 0;
+    while tamano >= 1024.0 && unidad < unidades.len() - 1 {
+        tamano /= 1024.0;
+        unidad += 1;
     }
-    notificar_carga(INFO, "ResideEn");
-    //cargar_reside_en(&profesores, &direcciones, &pool),
+    format!("{tamano:.1} {}", unidades[unidad])
+}
 
-    for s in &seguros {
-        let familiar = familiares.choose(&mut rng).unwrap();
-        AseguraA::new(s, familiar).insertar_en_db(&pool).await?;
+/// Calcula el plan de la carga e informa el resultado por STDOUT.
+fn informar_plan(args: &Args, formato: Formato) -> Result<(), Box<dyn Error>> {
+    let fuente = args.fuente_datasets();
+    let universidades = cargar_nombres(&UNIVERSIDADES, &fuente)?.len();
+    let titulos = TITULOS.leer::<Titulo>(&fuente)?.len();
+    let idiomas = cargar_nombres(&IDIOMAS, &fuente)?.len();
+    let plan = planificar(&args.opciones_carga(), universidades, titulos, idiomas);
+
+    match formato {
+        Formato::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+        Formato::Tabla => {
+            println!(
+                "{:>3} {:<34} {:>10} {:>21} {:>9} {:>11}  Origen",
+                "", "Tabla", "Filas", "Rango", "Bytes/fila", "Tamaño"
+            );
+            for (i, tabla) in plan.iter().enumerate() {
+                println!(
+                    "{:>3} {:<34} {:>10} {:>21} {:>9} {:>11}  {}",
+                    i + 1,
+                    tabla.tabla,
+                    tabla.filas.esperado,
+                    format!("{}..{}", tabla.filas.minimo, tabla.filas.maximo),
+                    tabla.bytes_por_fila,
+                    tamano_legible(tabla.bytes()),
+                    tabla.origen
+                );
+            }
+
+            let filas: usize = plan.iter().map(|t| t.filas.esperado).sum();
+            let bytes: usize = plan.iter().map(PlanTabla::bytes).sum();
+            println!(
+                "\nTotal: {filas} filas, {} sin contar índices.",
+                tamano_legible(bytes)
+            );
+            println!("\nDatasets:");
+            for dataset in DATASETS {
+                let tablas: Vec<&str> = plan
+                    .iter()
+                    .filter(|t| t.datasets.contains(&dataset.archivo))
+                    .map(|t| t.tabla)
+                    .collect();
+                println!(
+                    "    - {:<60} {}",
+                    dataset.origen(&fuente),
+                    tablas.join(", ")
+                );
+            }
+        }
     }
-    notificar_carga(INFO, "AseguraA");
-    //cargar_asegura_a(&seguros, &familiares, &pool),
-    //
-
-    generar_reporte().await;
     Ok(())
 }
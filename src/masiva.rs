@@ -0,0 +1,317 @@
+// Autor: Acosta Quintana, Lautaro
+//! Carga masiva: las filas de cada tabla se acumulan en memoria y se envían juntas, con
+//! `LOAD DATA LOCAL INFILE` en MySQL o con INSERT de muchas filas en el resto de los casos.
+
+use std::{any::Any, future::Future, path::PathBuf, pin::Pin};
+
+use dbdata::{Conexion, Insertable};
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
+
+use crate::{
+    conexion::{ConfigConexion, ModoTls},
+    insertar_fila, interrumpido, notificar_carga, sumar_al_contador, Interrupcion, Notificacion,
+};
+
+/// Filas de una tabla que se acumulan como máximo antes de enviarlas.
+pub const FILAS_POR_VOLCADO: usize = 10_000;
+
+/// Conexión aparte de MySQL con la que se envían las filas con `LOAD DATA LOCAL INFILE`, por el
+/// mismo servidor y socket que la conexión de la carga.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "carga-masiva"), allow(dead_code))]
+pub struct ConexionInfile {
+    url: String,
+    socket: Option<PathBuf>,
+}
+
+impl ConexionInfile {
+    /// La conexión configurada en `config`, o `None` si `config` pide cifrar la conexión: la
+    /// conexión de `LOAD DATA LOCAL INFILE` no se puede cifrar, así que en ese caso las filas se
+    /// envían con INSERT por la conexión de la carga.
+    pub fn new(config: &ConfigConexion) -> anyhow::Result<Option<Self>> {
+        if config.tls.is_some_and(|tls| tls != ModoTls::Deshabilitado) {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            url: config.url()?,
+            socket: config.socket.clone(),
+        }))
+    }
+}
+
+struct CargaMasiva {
+    /// Conexión con la que se usa `LOAD DATA LOCAL INFILE`. Deja de usarse, y las filas se envían
+    /// con INSERT, si el servidor no lo permite.
+    infile: Option<ConexionInfile>,
+    pendientes: Option<Box<dyn Pendientes>>,
+}
+
+static CARGA_MASIVA: Lazy<Mutex<Option<CargaMasiva>>> = Lazy::new(|| Mutex::new(None));
+
+/// Activa la carga masiva en las siguientes llamadas a [`crate::insertar`]. Con `infile`, la
+/// conexión con una base de datos MySQL, las tablas se cargan con `LOAD DATA LOCAL INFILE` si el programa se
+/// compiló con la feature `carga-masiva`.
+pub async fn activar_carga_masiva(infile: Option<ConexionInfile>) {
+    if infile.is_some() && cfg!(not(feature = "carga-masiva")) {
+        notificar_carga(
+            Notificacion::WARN,
+            "El programa se compiló sin la feature 'carga-masiva', por lo que no se usa LOAD DATA \
+            LOCAL INFILE sino INSERT de muchas filas.",
+        );
+    }
+    *CARGA_MASIVA.lock().await = Some(CargaMasiva {
+        infile: infile.filter(|_| cfg!(feature = "carga-masiva")),
+        pendientes: None,
+    });
+}
+
+/// Desactiva la carga masiva, descartando las filas acumuladas que no se enviaron.
+pub async fn desactivar_carga_masiva() {
+    *CARGA_MASIVA.lock().await = None;
+}
+
+pub async fn carga_masiva_activa() -> bool {
+    CARGA_MASIVA.lock().await.is_some()
+}
+
+/// Filas acumuladas de una tabla, sin importar su tipo.
+trait Pendientes: Send {
+    fn tabla(&self) -> &'static str;
+    fn cantidad(&self) -> usize;
+    fn como_any(&mut self) -> &mut dyn Any;
+    fn volcar<'a>(
+        self: Box<Self>,
+        conexion: &'a Conexion,
+        infile: &'a mut Option<ConexionInfile>,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<()>> + 'a>>;
+}
+
+struct LoteDe<T>(Vec<T>);
+
+impl<T> Pendientes for LoteDe<T>
+where
+    T: Insertable + Send + Sync + 'static,
+{
+    fn tabla(&self) -> &'static str {
+        T::TABLA
+    }
+
+    fn cantidad(&self) -> usize {
+        self.0.len()
+    }
+
+    fn como_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn volcar<'a>(
+        self: Box<Self>,
+        conexion: &'a Conexion,
+        infile: &'a mut Option<ConexionInfile>,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<()>> + 'a>> {
+        Box::pin(volcar(self.0, conexion, infile))
+    }
+}
+
+/// Acumula `fila` para enviarla junto con las demás filas de su tabla. Las filas acumuladas se
+/// envían al empezar otra tabla, al llegar a [`FILAS_POR_VOLCADO`] y al terminar la carga con
+/// [`volcar_pendientes`].
+///
+/// Si se interrumpió la carga se envían las filas ya acumuladas y se devuelve [`Interrupcion`].
+pub(crate) async fn acumular<T>(conexion: &Conexion, fila: &T) -> anyhow::Result<()>
+where
+    T: Insertable + Clone + Send + Sync + 'static,
+{
+    let mut estado = CARGA_MASIVA.lock().await;
+    let carga = estado
+        .as_mut()
+        .expect("la carga masiva debe estar activada");
+    if interrumpido() {
+        carga.volcar(conexion).await?;
+        return Err(Interrupcion.into());
+    }
+    if carga
+        .pendientes
+        .as_ref()
+        .is_some_and(|p| p.tabla() != T::TABLA)
+    {
+        carga.volcar(conexion).await?;
+    }
+    let pendientes = carga
+        .pendientes
+        .get_or_insert_with(|| Box::new(LoteDe::<T>(Vec::new())));
+    pendientes
+        .como_any()
+        .downcast_mut::<LoteDe<T>>()
+        .expect("las filas acumuladas son de otra tabla")
+        .0
+        .push(fila.clone());
+    if pendientes.cantidad() >= FILAS_POR_VOLCADO {
+        carga.volcar(conexion).await?;
+    }
+    Ok(())
+}
+
+/// Envía las filas que quedaron acumuladas. No hace nada si la carga masiva no está activada.
+pub async fn volcar_pendientes(conexion: &Conexion) -> anyhow::Result<()> {
+    match CARGA_MASIVA.lock().await.as_mut() {
+        Some(carga) => carga.volcar(conexion).await,
+        None => Ok(()),
+    }
+}
+
+impl CargaMasiva {
+    async fn volcar(&mut self, conexion: &Conexion) -> anyhow::Result<()> {
+        match self.pendientes.take() {
+            Some(pendientes) => pendientes.volcar(conexion, &mut self.infile).await,
+            None => Ok(()),
+        }
+    }
+}
+
+/// Envía `filas` con `LOAD DATA LOCAL INFILE` si se puede, o si no con INSERT de muchas filas.
+#[cfg_attr(not(feature = "carga-masiva"), allow(unused_variables))]
+async fn volcar<T>(
+    filas: Vec<T>,
+    conexion: &Conexion,
+    infile: &mut Option<ConexionInfile>,
+) -> anyhow::Result<()>
+where
+    T: Insertable + Send + Sync + 'static,
+{
+    #[cfg(feature = "carga-masiva")]
+    if let (Conexion::MySql(_), Some(destino)) = (conexion, infile.as_ref()) {
+        let filas: std::sync::Arc<[T]> = filas.into();
+        match infile::cargar(destino, filas.clone()).await {
+            Ok(cargadas) => {
+                contar(T::TABLA, filas.len(), cargadas).await;
+                return Ok(());
+            }
+            Err(err) => {
+                let motivo = if infile::no_permitido(&err) {
+                    "El servidor no permite LOAD DATA LOCAL INFILE".to_string()
+                } else {
+                    format!("Falló LOAD DATA LOCAL INFILE: {err}")
+                };
+                notificar_carga(
+                    Notificacion::WARN,
+                    &format!("{motivo}; el resto de la carga se hace con INSERT de muchas filas."),
+                );
+                *infile = None;
+                return insertar_en_lotes(&filas, conexion).await;
+            }
+        }
+    }
+    insertar_en_lotes(&filas, conexion).await
+}
+
+/// Inserta `filas` con INSERT de muchas filas que descartan las que no se pueden insertar. Si
+/// falla un lote, como cuando Postgres rechaza una fila que no cumple un CHECK, sus filas se
+/// insertan de a una como en la carga normal.
+async fn insertar_en_lotes<T>(filas: &[T], conexion: &Conexion) -> anyhow::Result<()>
+where
+    T: Insertable,
+{
+    // MySQL y Postgres admiten a lo sumo 65535 parámetros por consulta.
+    let por_lote = (65535 / T::COLUMNAS.len().max(1)).min(1000);
+    for lote in filas.chunks(por_lote) {
+        match conexion.insertar_lote(lote).await {
+            Ok(cargadas) => contar(T::TABLA, lote.len(), cargadas).await,
+            Err(err) => {
+                notificar_carga(
+                    Notificacion::WARN,
+                    &format!(
+                        "{err} (se insertan de a una las {} filas del lote de {})",
+                        lote.len(),
+                        T::TABLA
+                    ),
+                );
+                for fila in lote {
+                    insertar_fila(conexion, fila).await?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Cuenta las filas enviadas a `tabla` que se cargaron y las que se descartaron.
+async fn contar(tabla: &str, enviadas: usize, cargadas: u64) {
+    let cargadas = cargadas as usize;
+    let rechazadas = enviadas.saturating_sub(cargadas);
+    sumar_al_contador(Notificacion::INFO, tabla, cargadas).await;
+    if rechazadas > 0 {
+        notificar_carga(
+            Notificacion::WARN,
+            &format!("Se descartaron {rechazadas} de las {enviadas} filas enviadas a {tabla}."),
+        );
+        sumar_al_contador(Notificacion::WARN, tabla, rechazadas).await;
+    }
+}
+
+#[cfg(feature = "carga-masiva")]
+mod infile {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use dbdata::FilaTsv;
+    use futures_util::{stream, StreamExt};
+    use mysql_async::{prelude::Queryable, Opts, OptsBuilder};
+
+    use super::ConexionInfile;
+
+    /// Filas que se escriben en cada fragmento del archivo enviado al servidor.
+    const FILAS_POR_FRAGMENTO: usize = 500;
+
+    /// Carga `filas` con `LOAD DATA LOCAL INFILE` en una conexión aparte. Las filas se escriben
+    /// como TSV a medida que el servidor las pide, sin pasar por un archivo temporal. Devuelve la
+    /// cantidad de filas cargadas; con LOCAL, el servidor descarta las filas duplicadas o inválidas
+    /// en lugar de fallar.
+    pub async fn cargar<T>(destino: &ConexionInfile, filas: Arc<[T]>) -> anyhow::Result<u64>
+    where
+        T: FilaTsv + Send + Sync + 'static,
+    {
+        let opciones = OptsBuilder::from_opts(Opts::from_url(&destino.url)?).socket(
+            destino
+                .socket
+                .as_ref()
+                .map(|socket| socket.to_string_lossy().into_owned()),
+        );
+        let mut conexion = mysql_async::Conn::new(opciones).await?;
+        let cantidad = filas.len();
+        conexion.set_infile_handler(async move {
+            let fragmentos = (0..cantidad)
+                .step_by(FILAS_POR_FRAGMENTO)
+                .map(move |inicio| {
+                    let mut tsv = String::new();
+                    for fila in &filas[inicio..(inicio + FILAS_POR_FRAGMENTO).min(cantidad)] {
+                        fila.escribir_tsv(&mut tsv);
+                    }
+                    Ok(Bytes::from(tsv))
+                });
+            Ok(stream::iter(fragmentos).boxed())
+        });
+
+        let consulta = format!(
+            "LOAD DATA LOCAL INFILE '{tabla}' INTO TABLE {tabla} CHARACTER SET utf8mb4 \
+            FIELDS TERMINATED BY '\\t' ESCAPED BY '\\\\' LINES TERMINATED BY '\\n' ({})",
+            T::COLUMNAS.join(","),
+            tabla = T::TABLA,
+        );
+        let resultado = conexion.query_drop(consulta).await;
+        let cargadas = conexion.affected_rows();
+        conexion.disconnect().await?;
+        resultado?;
+        Ok(cargadas)
+    }
+
+    /// Indica si `error` se debe a que el servidor no permite `LOAD DATA LOCAL INFILE`.
+    pub fn no_permitido(error: &anyhow::Error) -> bool {
+        // ER_NOT_ALLOWED_COMMAND y ER_CLIENT_LOCAL_FILES_DISABLED.
+        matches!(
+            error.downcast_ref::<mysql_async::Error>(),
+            Some(mysql_async::Error::Server(error)) if matches!(error.code, 1148 | 3948)
+        )
+    }
+}
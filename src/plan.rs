@@ -0,0 +1,353 @@
+// Autor: Acosta Quintana, Lautaro
+//! Estimación de lo que generaría una carga sin conectarse con la base de datos.
+
+use serde::Serialize;
+
+use crate::{
+    carga::{OpcionesCarga, MAX_AUTORES},
+    datasets::{
+        ASEGURADORAS, CAJAS_PREVISIONALES, CATEGORIAS_INVESTIGACION, CERTIFICACIONES, IDIOMAS,
+        MATERIAS, OBRAS_SOCIALES, PERCEPCIONES, PROVINCIAS, TITULOS, UNIVERSIDADES,
+    },
+    distribucion::Distribucion,
+    esquema::{columnas, TABLAS},
+};
+
+/// Cantidad de filas que se estima generar en una tabla.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Estimacion {
+    pub minimo: usize,
+    pub esperado: usize,
+    pub maximo: usize,
+}
+
+impl Estimacion {
+    fn exacta(filas: usize) -> Self {
+        Self {
+            minimo: filas,
+            esperado: filas,
+            maximo: filas,
+        }
+    }
+
+    /// `base` filas más, por cada una de ellas, entre `min` y `max` filas elegidas de manera
+    /// uniforme.
+    fn por_cada(base: usize, min: usize, max: usize) -> Self {
+        Self {
+            minimo: base * min,
+            esperado: base * (min + max) / 2,
+            maximo: base * max,
+        }
+    }
+
+    /// Cantidad elegida de manera uniforme dentro de `min..max`, como `rng.gen_range(min..max)`.
+    fn rango(min: usize, max: usize) -> Self {
+        let max = max.saturating_sub(1).max(min);
+        Self {
+            minimo: min,
+            esperado: (min + max) / 2,
+            maximo: max,
+        }
+    }
+
+    fn mas(self, otra: Self) -> Self {
+        Self {
+            minimo: self.minimo + otra.minimo,
+            esperado: self.esperado + otra.esperado,
+            maximo: self.maximo + otra.maximo,
+        }
+    }
+}
+
+/// Lo que se generaría en una tabla.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanTabla {
+    pub tabla: &'static str,
+    pub filas: Estimacion,
+    /// Tamaño máximo de una fila según los tipos de las columnas de la migración de MySQL.
+    pub bytes_por_fila: usize,
+    /// Cómo se calcula la cantidad de filas.
+    pub origen: String,
+    /// Archivos de los datasets de los que se toman valores.
+    pub datasets: Vec<&'static str>,
+}
+
+impl PlanTabla {
+    /// Bytes estimados de la tabla con la cantidad esperada de filas.
+    pub fn bytes(&self) -> usize {
+        self.filas.esperado * self.bytes_por_fila
+    }
+}
+
+/// Bytes que ocupa como máximo un valor de `tipo` en MySQL, suponiendo un byte por carácter.
+pub fn bytes_de_tipo(tipo: &str) -> usize {
+    let (nombre, parametros) = match tipo.split_once('(') {
+        Some((nombre, resto)) => (nombre, resto.trim_end_matches(')')),
+        None => (tipo, ""),
+    };
+    let largo = parametros
+        .split(',')
+        .next()
+        .and_then(|p| p.trim().parse::<usize>().ok());
+    match nombre {
+        "tinyint" | "bool" | "boolean" | "year" | "enum" => 1,
+        "smallint" => 2,
+        "date" | "time" | "mediumint" => 3,
+        "int" | "integer" => 4,
+        "bigint" | "datetime" | "double" => 8,
+        "char" => largo.unwrap_or(1),
+        "varchar" => {
+            let largo = largo.unwrap_or(0);
+            largo + if largo > 255 { 2 } else { 1 }
+        }
+        // Cada 9 dígitos ocupan 4 bytes; por defecto son 10 dígitos.
+        "decimal" | "numeric" => largo.unwrap_or(10).div_ceil(9) * 4,
+        _ => 8,
+    }
+}
+
+/// Bytes que ocupa como máximo una fila de `tabla`.
+fn bytes_por_fila(tabla: &str) -> usize {
+    columnas(tabla).iter().map(|c| bytes_de_tipo(&c.tipo)).sum()
+}
+
+/// Estima las filas de una relación configurable a partir de la distribución y la cardinalidad
+/// con las que se generan.
+fn estimar_relacion(
+    relacion: &str,
+    defecto: Distribucion,
+    padres: usize,
+    hijos: usize,
+    opciones: &OpcionesCarga,
+) -> (Estimacion, String) {
+    let distribucion = opciones.distribuciones.de(relacion, defecto);
+    // Probabilidad de que un padre tenga cada cantidad de hijos, cuando se puede calcular.
+    let probabilidades: Option<Vec<f64>> = match distribucion {
+        Distribucion::Fija { min, max } => Some(
+            (0..=max)
+                .map(|k| {
+                    if k >= min {
+                        1.0 / (max - min + 1) as f64
+                    } else {
+                        0.0
+                    }
+                })
+                .collect(),
+        ),
+        Distribucion::Uniforme if padres > 0 => {
+            // Aproximación de Poisson de la binomial de los hijos de cada padre.
+            let lambda = hijos as f64 / padres as f64;
+            let mut p = (-lambda).exp();
+            let mut probabilidades = Vec::new();
+            for k in 0..=(lambda * 4.0).ceil() as usize + 10 {
+                probabilidades.push(p);
+                p *= lambda / (k + 1) as f64;
+            }
+            Some(probabilidades)
+        }
+        _ => None,
+    };
+
+    let mut estimacion = match distribucion {
+        Distribucion::Uniforme | Distribucion::Zipf { .. } => Estimacion::exacta(hijos),
+        Distribucion::Fija { min, max } => Estimacion::por_cada(padres, min, max),
+        Distribucion::Normal { media, desvio } => Estimacion {
+            minimo: (padres as f64 * (media - 3.0 * desvio).max(0.0)) as usize,
+            esperado: (padres as f64 * media) as usize,
+            maximo: (padres as f64 * (media + 3.0 * desvio)).ceil() as usize,
+        },
+    };
+    let mut origen = format!("{distribucion} sobre {padres} padres");
+
+    if let Some(c) = opciones.cardinalidades.de(relacion) {
+        origen.push_str(&format!(", cardinalidad {}..", c.min));
+        // Se descartan los hijos que superan el máximo y se agregan los que faltan para el
+        // mínimo, que en el peor caso son `min` por cada padre que la distribución deja sin hijos.
+        let minimo_por_padre = match distribucion {
+            Distribucion::Fija { min, .. } => min,
+            _ => 0,
+        };
+        estimacion.maximo += padres * c.min.saturating_sub(minimo_por_padre);
+        if let Some(max) = c.max {
+            origen.push_str(&max.to_string());
+            estimacion.minimo = estimacion.minimo.min(padres * max);
+            estimacion.maximo = estimacion.maximo.min(padres * max);
+        }
+        estimacion.minimo = estimacion.minimo.max(padres * c.min);
+        estimacion.esperado = match &probabilidades {
+            Some(probabilidades) => {
+                let por_padre: f64 = probabilidades
+                    .iter()
+                    .enumerate()
+                    .map(|(k, p)| p * k.max(c.min).min(c.max.unwrap_or(usize::MAX)) as f64)
+                    .sum();
+                (por_padre * padres as f64).round() as usize
+            }
+            None => estimacion.esperado,
+        }
+        .clamp(estimacion.minimo, estimacion.maximo);
+    }
+    (estimacion, origen)
+}
+
+/// Estima, sin conectarse con la base de datos, las filas de cada tabla en el orden en que las
+/// carga [`crate::carga::cargar`], a partir de las opciones de la carga y de la cantidad de
+/// universidades, títulos e idiomas de los datasets.
+pub fn planificar(
+    opciones: &OpcionesCarga,
+    universidades: usize,
+    titulos: usize,
+    idiomas: usize,
+) -> Vec<PlanTabla> {
+    let m = opciones.muestras;
+    let cantidad = |tabla: &str| opciones.cantidad(tabla);
+    let origen = |tabla: &str| {
+        if opciones.cantidades.contains_key(tabla) {
+            "cantidad de la tabla"
+        } else {
+            "muestras"
+        }
+    };
+    let profesores = cantidad("Profesores");
+    let publicaciones = cantidad("Publicaciones");
+    let instituciones = universidades.min(cantidad("Instituciones"));
+    let cantidad_titulos = titulos.min(cantidad("Titulos"));
+    let relacion = |nombre: &str, defecto: Distribucion, hijos: usize| {
+        estimar_relacion(nombre, defecto, profesores, hijos, opciones)
+    };
+    let una_por_profesor = Distribucion::Fija { min: 1, max: 1 };
+
+    TABLAS
+        .iter()
+        .map(|&tabla| {
+            let (filas, origen, datasets): (Estimacion, String, Vec<&'static str>) = match tabla {
+                "Direcciones" => (
+                    Estimacion::exacta(cantidad(tabla)),
+                    origen(tabla).to_string(),
+                    vec![PROVINCIAS.archivo],
+                ),
+                "ObrasSociales" => (
+                    Estimacion::exacta(cantidad(tabla) + 1),
+                    format!("{} + D.A.S.U.Te.N", origen(tabla)),
+                    vec![OBRAS_SOCIALES.archivo],
+                ),
+                "Titulos" => (
+                    Estimacion::exacta(cantidad_titulos),
+                    format!("mínimo entre la cantidad y {titulos} títulos"),
+                    vec![TITULOS.archivo],
+                ),
+                "Percepciones" => (
+                    Estimacion::exacta(cantidad(tabla)),
+                    origen(tabla).to_string(),
+                    vec![PERCEPCIONES.archivo, CAJAS_PREVISIONALES.archivo],
+                ),
+                "Seguros" => (
+                    Estimacion::exacta(cantidad(tabla)),
+                    origen(tabla).to_string(),
+                    vec![ASEGURADORAS.archivo],
+                ),
+                "CursosConferencias" | "Horarios" => (
+                    Estimacion::exacta(cantidad(tabla)),
+                    origen(tabla).to_string(),
+                    vec![MATERIAS.archivo],
+                ),
+                "ActividadesInvestigacion" => (
+                    Estimacion::exacta(cantidad(tabla)),
+                    origen(tabla).to_string(),
+                    vec![CATEGORIAS_INVESTIGACION.archivo],
+                ),
+                "Idiomas" => (
+                    Estimacion::exacta(idiomas),
+                    "todos los idiomas, si la tabla está vacía".to_string(),
+                    vec![IDIOMAS.archivo],
+                ),
+                "Instituciones" => (
+                    Estimacion::exacta(instituciones),
+                    format!("mínimo entre la cantidad y {universidades} universidades"),
+                    vec![UNIVERSIDADES.archivo],
+                ),
+                "Contactos" | "AtendioA" | "ResideEn" => {
+                    let (filas, origen) = relacion(tabla, una_por_profesor, profesores);
+                    (filas, origen, vec![])
+                }
+                "DependenciasEmpresas"
+                | "Familiares"
+                | "DocObraSocial"
+                | "DeclaracionesJuradas" => {
+                    let (filas, origen) = relacion(tabla, Distribucion::Uniforme, cantidad(tabla));
+                    (filas, origen, vec![])
+                }
+                "Publico" => {
+                    // Cada publicación tiene lugar para 1..MAX_AUTORES profesores, y la
+                    // distribución reparte tantas autorías como lugares haya.
+                    let lugares = Estimacion::por_cada(publicaciones, 1, MAX_AUTORES);
+                    let autorias = |hijos| relacion(tabla, Distribucion::Uniforme, hijos).0;
+                    let (filas, origen) = relacion(tabla, Distribucion::Uniforme, lugares.esperado);
+                    let filas = Estimacion {
+                        minimo: autorias(lugares.minimo).minimo.min(lugares.maximo),
+                        esperado: filas.esperado.min(lugares.maximo),
+                        maximo: autorias(lugares.maximo).maximo.min(lugares.maximo),
+                    };
+                    (
+                        filas,
+                        format!("{origen}, 1..{MAX_AUTORES} autores por publicación"),
+                        vec![],
+                    )
+                }
+                "ConoceIdioma" => (
+                    Estimacion::exacta(profesores).mas(Estimacion::por_cada(profesores, 1, 2)),
+                    "español más 1..2 idiomas por profesor".to_string(),
+                    vec![IDIOMAS.archivo, CERTIFICACIONES.archivo],
+                ),
+                "SeDaIdioma" => (
+                    Estimacion::por_cada(instituciones, 1, 2),
+                    "1..2 idiomas por institución".to_string(),
+                    vec![IDIOMAS.archivo],
+                ),
+                "SeDaTitulo" => (
+                    Estimacion::por_cada(instituciones, 1, 4),
+                    "1..4 títulos por institución".to_string(),
+                    vec![],
+                ),
+                "PoseeTitulo" => (
+                    Estimacion::exacta(profesores).mas(Estimacion::rango(0, m)),
+                    "un terciario por profesor más 0..muestras".to_string(),
+                    vec![],
+                ),
+                "Beneficia" | "RealizaInves" | "RealizoAct" => (
+                    Estimacion::rango(cantidad(tabla) / 2, cantidad(tabla)),
+                    format!("{0}/2..{0}", origen(tabla)),
+                    vec![],
+                ),
+                "ReferenciaBibliografica" => (
+                    // Las publicaciones del año más antiguo no tienen a quién citar.
+                    Estimacion {
+                        minimo: 0,
+                        ..Estimacion::por_cada(
+                            publicaciones,
+                            opciones.citas.min,
+                            opciones.citas.max,
+                        )
+                    },
+                    format!(
+                        "{} referencias por publicación, a publicaciones anteriores",
+                        opciones.citas
+                    ),
+                    vec![],
+                ),
+                _ => (
+                    Estimacion::exacta(cantidad(tabla)),
+                    origen(tabla).to_string(),
+                    vec![],
+                ),
+            };
+            PlanTabla {
+                tabla,
+                filas,
+                bytes_por_fila: bytes_por_fila(tabla),
+                origen,
+                datasets,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,100 @@
+// Autor: Acosta Quintana, Lautaro
+//! Bases de datos de prueba con datos conocidos, para los tests de integración que necesitan el
+//! esquema cargado. Sólo se compila con la feature `prueba`.
+//!
+//! Los datos se generan siempre con la misma semilla, por lo que los tests pueden referirse a los
+//! DNI y los IDs de las filas devueltas. Con `#[sqlx::test]` hay que desactivar sus migraciones,
+//! porque la carga ejecuta la del motor correspondiente:
+//!
+//! ```text
+//! #[sqlx::test(migrations = false)]
+//! async fn profesores(pool: MySqlPool) -> anyhow::Result<()> {
+//!     let datos = prueba::cargar(pool.clone()).await?;
+//!     let dni = &datos.profesores[0].dni;
+//!     ...
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use dbdata::Conexion;
+
+use crate::{carga::Generados, destino::Memoria, generador::Generador};
+
+/// Cantidad de registros por tabla de [`cargar`].
+pub const MUESTRAS: usize = 20;
+
+/// Semilla de [`cargar`].
+pub const SEMILLA: u64 = 0;
+
+/// [`Generador`] de los datos de prueba: [`MUESTRAS`] registros por tabla generados con
+/// [`SEMILLA`], en una base de datos con cualquier nombre.
+pub fn generador(pool: impl Into<Conexion>) -> Generador {
+    Generador::new(pool.into())
+        .muestras(MUESTRAS)
+        .semilla(SEMILLA)
+        .forzar(true)
+}
+
+/// Ejecuta la migración, carga los datos de prueba y devuelve las filas que la base de datos
+/// aceptó.
+pub async fn cargar(pool: impl Into<Conexion>) -> anyhow::Result<Generados> {
+    cargar_con(pool, |generador| generador).await
+}
+
+/// Como [`cargar`], pero con los cambios de `configurar` sobre [`generador`], como otra semilla
+/// o la cantidad de filas de algunas tablas. Con la carga masiva se devuelven todas las filas
+/// generadas, porque se envían a la base de datos después de generarlas.
+pub async fn cargar_con(
+    pool: impl Into<Conexion>,
+    configurar: impl FnOnce(Generador) -> Generador,
+) -> anyhow::Result<Generados> {
+    let memoria = Arc::new(Memoria::new());
+    configurar(generador(pool))
+        .destino(memoria.clone())
+        .ejecutar()
+        .await?;
+    Ok(aceptadas(&memoria))
+}
+
+fn aceptadas(memoria: &Memoria) -> Generados {
+    Generados {
+        direcciones: memoria.filas(),
+        titulos: memoria.filas(),
+        publicaciones: memoria.filas(),
+        reuniones_cientificas: memoria.filas(),
+        percepciones: memoria.filas(),
+        seguros: memoria.filas(),
+        obras_sociales: memoria.filas(),
+        idiomas: memoria.filas(),
+        empleadores: memoria.filas(),
+        instituciones: memoria.filas(),
+        cursos_conferencias: memoria.filas(),
+        actividades_extension_universitaria: memoria.filas(),
+        actividades_investigacion: memoria.filas(),
+        profesores: memoria.filas(),
+        contactos: memoria.filas(),
+        dependencias_empresas: memoria.filas(),
+        familiares: memoria.filas(),
+        doc_obra_social: memoria.filas(),
+        declaraciones_juradas: memoria.filas(),
+        declaraciones_de_cargo: memoria.filas(),
+        antecedentes_profesionales: memoria.filas(),
+        antecedentes_docentes: memoria.filas(),
+        horarios: memoria.filas(),
+        atendio_a: memoria.filas(),
+        conoce_idioma: memoria.filas(),
+        se_da_idioma: memoria.filas(),
+        beneficia: memoria.filas(),
+        posee_titulo: memoria.filas(),
+        se_da_titulo: memoria.filas(),
+        realiza_inves: memoria.filas(),
+        realizo_act: memoria.filas(),
+        referencia_bibliografica: memoria.filas(),
+        publico: memoria.filas(),
+        participo_en_reunion: memoria.filas(),
+        percibe_en: memoria.filas(),
+        reside_en: memoria.filas(),
+        asegura_a: memoria.filas(),
+    }
+}
@@ -0,0 +1,86 @@
+// Autor: Acosta Quintana, Lautaro
+//! Reintentos de las inserciones que fallan por errores transitorios de la base de datos.
+
+use std::{sync::RwLock, time::Duration};
+
+use rand::Rng;
+use sqlx::{mysql::MySqlDatabaseError, postgres::PgDatabaseError};
+
+/// Cuántas veces y con qué espera se reintenta una inserción que falló por un error transitorio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reintentos {
+    /// Cantidad máxima de reintentos de cada fila; con 0 no se reintenta.
+    pub maximo: u32,
+    /// Espera antes del primer reintento, que se duplica en cada uno de los siguientes.
+    pub espera_inicial: Duration,
+    /// Espera máxima entre dos reintentos.
+    pub espera_maxima: Duration,
+}
+
+impl Reintentos {
+    pub const POR_DEFECTO: Self = Self {
+        maximo: 5,
+        espera_inicial: Duration::from_millis(100),
+        espera_maxima: Duration::from_secs(5),
+    };
+
+    /// Espera antes del reintento número `intento` (empezando por 0): el doble que la anterior,
+    /// sin superar `espera_maxima`, y con una parte al azar para que las inserciones que fallaron
+    /// juntas no se reintenten todas a la vez.
+    pub fn espera<R: Rng + ?Sized>(&self, intento: u32, rng: &mut R) -> Duration {
+        let espera = self
+            .espera_inicial
+            .saturating_mul(2u32.saturating_pow(intento))
+            .min(self.espera_maxima);
+        espera / 2 + espera.mul_f64(rng.gen::<f64>() / 2.0)
+    }
+}
+
+impl Default for Reintentos {
+    fn default() -> Self {
+        Self::POR_DEFECTO
+    }
+}
+
+/// Política de reintentos con la que se insertan las filas.
+pub static REINTENTOS: RwLock<Reintentos> = RwLock::new(Reintentos::POR_DEFECTO);
+
+pub fn configurar_reintentos(reintentos: Reintentos) {
+    *REINTENTOS.write().unwrap() = reintentos;
+}
+
+pub fn reintentos() -> Reintentos {
+    *REINTENTOS.read().unwrap()
+}
+
+/// Indica si `error` es transitorio, es decir, si la misma inserción puede funcionar al
+/// reintentarla: conexiones caídas, tiempos de espera agotados, bloqueos y deadlocks. Los errores
+/// permanentes, como las claves duplicadas o los CHECK que no se cumplen, no se reintentan.
+pub fn es_transitorio(error: &anyhow::Error) -> bool {
+    let Some(error) = error.downcast_ref::<sqlx::Error>() else {
+        return false;
+    };
+    match error {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(error) => {
+            if let Some(error) = error.try_downcast_ref::<MySqlDatabaseError>() {
+                // Deadlock, lock wait timeout y demasiadas conexiones. La conexión perdida la
+                // detecta el cliente y llega como `sqlx::Error::Io`.
+                return matches!(error.number(), 1213 | 1205 | 1040);
+            }
+            if let Some(error) = error.try_downcast_ref::<PgDatabaseError>() {
+                let codigo = error.code();
+                // Errores de conexión, deadlock, serialización, bloqueo no disponible, demasiadas
+                // conexiones y el servidor apagándose.
+                return codigo.starts_with("08")
+                    || matches!(codigo, "40P01" | "40001" | "55P03" | "53300" | "57P01");
+            }
+            // SQLite informa el código extendido; el primario está en el byte más bajo.
+            error
+                .code()
+                .and_then(|codigo| codigo.parse::<i32>().ok())
+                .is_some_and(|codigo| matches!(codigo & 0xff, 5 | 6))
+        }
+        _ => false,
+    }
+}
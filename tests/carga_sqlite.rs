@@ -0,0 +1,44 @@
+// Autor: Acosta Quintana, Lautaro
+mod common;
+
+use carga_datos::{
+    cardinalidad::comprobar_cardinalidades, carga::OpcionesCarga, contar_filas, esquema::TABLAS,
+    migrar,
+};
+
+use common::{generar, sqlite_en_memoria};
+
+#[tokio::test]
+async fn carga_las_37_tablas_en_sqlite() {
+    let conexion = sqlite_en_memoria().await;
+    migrar(&conexion).await.unwrap();
+
+    generar(&conexion, 50, 9).await;
+
+    for tabla in TABLAS {
+        let filas = contar_filas(&conexion, tabla).await.unwrap();
+        assert!(filas > 0, "La tabla {tabla} quedó vacía");
+    }
+    assert_eq!(contar_filas(&conexion, "Profesores").await.unwrap(), 50);
+
+    for informe in comprobar_cardinalidades(&OpcionesCarga::default().cardinalidades, &conexion)
+        .await
+        .unwrap()
+    {
+        assert!(informe.se_cumple(), "No se cumple {}", informe.cardinalidad);
+    }
+}
+
+#[tokio::test]
+async fn carga_con_muy_pocas_muestras() {
+    for muestras in 0..=2 {
+        let conexion = sqlite_en_memoria().await;
+        migrar(&conexion).await.unwrap();
+
+        generar(&conexion, muestras, 9).await;
+        assert_eq!(
+            contar_filas(&conexion, "Profesores").await.unwrap(),
+            muestras as i64
+        );
+    }
+}
@@ -0,0 +1,110 @@
+// Autor: Acosta Quintana, Lautaro
+mod common;
+
+use std::collections::HashSet;
+
+use carga_datos::{
+    carga::OpcionesCarga,
+    citas::{parsear_atraccion, parsear_referencias, Citas},
+    destino::Memoria,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use common::generar_con;
+
+fn anios(cantidad: usize, rng: &mut StdRng) -> Vec<i32> {
+    (0..cantidad).map(|_| rng.gen_range(1990..2024)).collect()
+}
+
+/// Cantidad de citas que recibe la publicación más citada.
+fn mas_citada(citas: &[(usize, usize)], publicaciones: usize) -> usize {
+    let mut recibidas = vec![0; publicaciones];
+    citas.iter().for_each(|&(fuente, _)| recibidas[fuente] += 1);
+    recibidas.into_iter().max().unwrap_or(0)
+}
+
+#[test]
+fn solo_cita_publicaciones_anteriores() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let anios = anios(500, &mut rng);
+    let config = Citas {
+        min: 2,
+        max: 6,
+        atraccion: 1.0,
+    };
+    let citas = config.generar(&anios, &mut rng);
+    assert!(!citas.is_empty());
+
+    let mut pares = HashSet::new();
+    for &(fuente, citador) in &citas {
+        assert!(anios[fuente] < anios[citador], "{fuente} -> {citador}");
+        assert!(pares.insert((fuente, citador)), "{fuente} -> {citador}");
+    }
+    for (citador, anio) in anios.iter().enumerate() {
+        let referencias = citas.iter().filter(|&&(_, c)| c == citador).count();
+        let anteriores = anios.iter().filter(|a| *a < anio).count();
+        assert!(referencias <= config.max);
+        // Con muchas publicaciones anteriores siempre se alcanza el mínimo.
+        if anteriores >= 50 {
+            assert!(referencias >= config.min, "{citador}: {referencias}");
+        }
+    }
+}
+
+#[test]
+fn la_atraccion_reparte_las_citas() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let anios = anios(2000, &mut rng);
+    let concentradas = Citas {
+        min: 3,
+        max: 3,
+        atraccion: 0.1,
+    }
+    .generar(&anios, &mut rng);
+    let repartidas = Citas {
+        min: 3,
+        max: 3,
+        atraccion: 1000.0,
+    }
+    .generar(&anios, &mut rng);
+    assert!(mas_citada(&concentradas, anios.len()) > 2 * mas_citada(&repartidas, anios.len()));
+}
+
+#[test]
+fn sin_publicaciones_anteriores_no_hay_citas() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let config = Citas::default();
+    assert!(config.generar(&[], &mut rng).is_empty());
+    assert!(config.generar(&[2000], &mut rng).is_empty());
+    assert!(config.generar(&[2000; 20], &mut rng).is_empty());
+}
+
+#[tokio::test]
+async fn carga_una_sola_publicacion() {
+    let mut opciones = OpcionesCarga {
+        muestras: 30,
+        semilla: Some(2),
+        ..Default::default()
+    };
+    opciones.cantidades.insert("Publicaciones".to_string(), 1);
+    let generados = generar_con(&Memoria::new(), &opciones).await.unwrap();
+    assert_eq!(generados.publicaciones.len(), 1);
+    assert!(generados.referencia_bibliografica.is_empty());
+}
+
+#[test]
+fn interpreta_las_referencias() {
+    assert_eq!(parsear_referencias("1..5"), Ok((1, 5)));
+    assert_eq!(parsear_referencias(" 0 .. 0 "), Ok((0, 0)));
+    assert!(parsear_referencias("5..1").is_err());
+    assert!(parsear_referencias("3").is_err());
+}
+
+#[test]
+fn interpreta_la_atraccion() {
+    assert_eq!(parsear_atraccion("0"), Ok(0.0));
+    assert_eq!(parsear_atraccion(" 2.5 "), Ok(2.5));
+    for invalida in ["-1", "NaN", "inf", "-inf", "x"] {
+        assert!(parsear_atraccion(invalida).is_err(), "{invalida}");
+    }
+}
@@ -0,0 +1,44 @@
+// Autor: Acosta Quintana, Lautaro
+//! Funciones compartidas por los tests de integración. Cada test usa sólo algunas.
+#![allow(dead_code)]
+
+use carga_datos::{
+    carga::{cargar, Generados, OpcionesCarga},
+    conexion::{conectar, ConfigConexion, Motor},
+    datasets::{Datasets, FuenteDatasets},
+    destino::Destino,
+};
+use dbdata::Conexion;
+
+/// Conexión a una base de datos SQLite nueva en memoria, sin migrar.
+pub async fn sqlite_en_memoria() -> Conexion {
+    conectar(
+        Motor::Sqlite,
+        &ConfigConexion {
+            url: Some("sqlite::memory:".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+}
+
+/// Genera `muestras` registros por tabla con `semilla` a partir de los datasets incluidos y los
+/// envía a `destino`.
+pub async fn generar(destino: &dyn Destino, muestras: usize, semilla: u64) -> Generados {
+    let opciones = OpcionesCarga {
+        muestras,
+        semilla: Some(semilla),
+        ..Default::default()
+    };
+    generar_con(destino, &opciones).await.unwrap()
+}
+
+/// Como [`generar`], pero con otras opciones y devolviendo el error de la carga.
+pub async fn generar_con(
+    destino: &dyn Destino,
+    opciones: &OpcionesCarga,
+) -> anyhow::Result<Generados> {
+    let datasets = Datasets::cargar(&FuenteDatasets::default()).unwrap();
+    cargar(destino, datasets, opciones).await
+}
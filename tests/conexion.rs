@@ -0,0 +1,16 @@
+// Autor: Acosta Quintana, Lautaro
+use carga_datos::conexion::{conectar, ConfigConexion, Motor};
+
+#[tokio::test]
+async fn no_muestra_la_contrasena_en_los_errores() {
+    let config = ConfigConexion {
+        url: Some("postgres://usuario:secr@to@localhost:puerto/tpi".to_string()),
+        ..Default::default()
+    };
+    let error = conectar(Motor::Postgres, &config)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("usuario:***@localhost"), "{error}");
+    assert!(!error.contains("secr") && !error.contains("to@"), "{error}");
+}
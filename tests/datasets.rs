@@ -0,0 +1,152 @@
+// Autor: Acosta Quintana, Lautaro
+mod common;
+
+use carga_datos::{
+    carga::{Generados, OpcionesCarga},
+    datasets::{Datasets, ErrorDataset, FuenteDatasets, OBRAS_SOCIALES},
+    db_tablas::{ConoceIdioma, ObrasSociales},
+    destino::Memoria,
+};
+use dbdata::{Fila, Valor};
+
+use common::{generar, generar_con};
+
+#[tokio::test]
+async fn toma_el_vocabulario_de_los_datasets() {
+    let datasets = Datasets::cargar(&FuenteDatasets::default()).unwrap();
+    let materias = datasets.materias.clone();
+    let titulos = datasets.titulos.clone();
+    let categorias = datasets.categorias_investigacion.clone();
+    let certificaciones = datasets.certificaciones.clone();
+    let percepciones = datasets.percepciones.clone();
+
+    let memoria = Memoria::new();
+    let generados = generar(&memoria, 50, 3).await;
+
+    assert!(generados.titulos.iter().all(|t| titulos
+        .iter()
+        .any(|d| d.nivel == t.nivel && d.nombre == t.titulo)));
+    assert!(generados
+        .horarios
+        .iter()
+        .all(|h| materias.contains(&h.nombre_catedra)));
+    assert!(generados
+        .cursos_conferencias
+        .iter()
+        .all(|c| materias.iter().any(|m| c.nombre_curso.contains(m.as_str()))));
+    assert!(generados
+        .actividades_investigacion
+        .iter()
+        .all(|a| categorias.contains(&a.categoria)));
+    assert!(generados.percepciones.iter().all(|p| percepciones
+        .iter()
+        .any(|d| d.tipo == p.tipo && d.regimen == p.regimen && d.causa == p.causa)));
+
+    // Las certificaciones corresponden al idioma y acreditan su nivel.
+    for fila in memoria.filas::<ConoceIdioma>() {
+        let [_, Valor::Texto(idioma), Valor::Texto(certificacion), Valor::Texto(nivel)] =
+            &fila.valores()[..]
+        else {
+            panic!("{fila:?}");
+        };
+        let valida = match certificacion.as_str() {
+            "Lengua materna" => idioma == "Español" && nivel == "C2",
+            "Sin certificación" => !certificaciones.iter().any(|c| &c.idioma == idioma),
+            _ => certificaciones
+                .iter()
+                .any(|c| &c.idioma == idioma && &c.nombre == certificacion && &c.nivel == nivel),
+        };
+        assert!(valida, "{fila:?}");
+    }
+}
+
+async fn con_ficticias(muestras: usize, ficticias: f64) -> anyhow::Result<Generados> {
+    let opciones = OpcionesCarga {
+        muestras,
+        semilla: Some(5),
+        ficticias,
+        ..Default::default()
+    };
+    generar_con(&Memoria::new(), &opciones).await
+}
+
+#[tokio::test]
+async fn mezcla_entidades_reales_y_ficticias() {
+    let datasets = Datasets::cargar(&FuenteDatasets::default()).unwrap();
+    let es_real = |o: &ObrasSociales| {
+        datasets
+            .obras_sociales
+            .iter()
+            .any(|d| d.nombre == o.nombre_obra && d.rnos == o.id_obrasocial)
+    };
+    let reales = |g: &Generados| g.obras_sociales.iter().filter(|o| es_real(o)).count();
+
+    // Sin ficticias se usan todas las reales y se inventan sólo las que faltan.
+    let generados = con_ficticias(50, 0.0).await.unwrap();
+    assert_eq!(reales(&generados), datasets.obras_sociales.len());
+    assert_eq!(generados.obras_sociales.len(), 51);
+    assert!(generados
+        .seguros
+        .iter()
+        .take(datasets.aseguradoras.len())
+        .all(|s| datasets.aseguradoras.contains(&s.compania_aseguradora)));
+    assert!(generados
+        .percepciones
+        .iter()
+        .all(|p| datasets.cajas_previsionales.contains(&p.institucion_caja)));
+
+    let generados = con_ficticias(50, 1.0).await.unwrap();
+    assert_eq!(reales(&generados), 0);
+
+    assert!(con_ficticias(50, 1.5).await.is_err());
+}
+
+#[tokio::test]
+async fn siempre_hay_un_titulo_terciario() {
+    let generar_titulos = |titulos: usize| async move {
+        let mut opciones = OpcionesCarga {
+            muestras: 20,
+            semilla: Some(4),
+            ..Default::default()
+        };
+        opciones.cantidades.insert("Titulos".to_string(), titulos);
+        generar_con(&Memoria::new(), &opciones).await
+    };
+
+    let generados = generar_titulos(1).await.unwrap();
+    assert_eq!(generados.titulos.len(), 1);
+    assert_eq!(generados.titulos[0].nivel, "Terciario");
+    assert_eq!(generados.posee_titulo.len(), generados.profesores.len());
+
+    let generados = generar_titulos(2).await.unwrap();
+    let niveles: Vec<&str> = generados.titulos.iter().map(|t| t.nivel.as_str()).collect();
+    assert_eq!(niveles[0], "Terciario");
+    assert_ne!(niveles[1], "Terciario");
+
+    assert!(generar_titulos(0).await.is_err());
+}
+
+#[test]
+fn rechaza_obras_sociales_con_el_mismo_rnos() {
+    let directorio = std::env::temp_dir().join(format!("datasets-{}", std::process::id()));
+    std::fs::create_dir_all(&directorio).unwrap();
+    std::fs::write(
+        directorio.join(OBRAS_SOCIALES.archivo),
+        "RNOS;Nombre\n100;Obra Social Uno\n200;Obra Social Dos\n100;Obra Social Tres\n",
+    )
+    .unwrap();
+
+    let verificacion = OBRAS_SOCIALES.verificar(&FuenteDatasets {
+        directorio: Some(directorio.clone()),
+    });
+    std::fs::remove_dir_all(&directorio).unwrap();
+    assert_eq!(verificacion.filas, 3);
+    assert!(
+        matches!(
+            &verificacion.problemas[..],
+            [ErrorDataset::Duplicado { linea: 4, primera: 2, valor, .. }] if valor == "100"
+        ),
+        "{:?}",
+        verificacion.problemas
+    );
+}
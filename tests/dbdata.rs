@@ -0,0 +1,41 @@
+// Autor: Acosta Quintana, Lautaro
+mod common;
+
+use carga_datos::{contar_filas, db_tablas::Idiomas, migrar};
+use dbdata::{Conexion, DBData};
+
+use common::sqlite_en_memoria;
+
+#[tokio::test]
+async fn inserta_en_transacciones_y_conexiones() {
+    let conexion = sqlite_en_memoria().await;
+    migrar(&conexion).await.unwrap();
+    let Conexion::Sqlite(pool) = &conexion else {
+        unreachable!()
+    };
+
+    let mut transaccion = pool.begin().await.unwrap();
+    Idiomas::new("Guaraní")
+        .insertar_en_db(&mut *transaccion)
+        .await
+        .unwrap();
+    transaccion.rollback().await.unwrap();
+    assert_eq!(contar_filas(&conexion, "Idiomas").await.unwrap(), 0);
+
+    let mut transaccion = pool.begin().await.unwrap();
+    Idiomas::new("Guaraní")
+        .insertar_en_db(&mut *transaccion)
+        .await
+        .unwrap();
+    transaccion.commit().await.unwrap();
+    assert_eq!(contar_filas(&conexion, "Idiomas").await.unwrap(), 1);
+
+    {
+        let mut conexion_unica = pool.acquire().await.unwrap();
+        Idiomas::new("Qom")
+            .insertar_en_db(&mut *conexion_unica)
+            .await
+            .unwrap();
+    }
+    assert_eq!(contar_filas(&conexion, "Idiomas").await.unwrap(), 2);
+}
@@ -0,0 +1,122 @@
+// Autor: Acosta Quintana, Lautaro
+mod common;
+
+use std::{
+    fs,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use carga_datos::{
+    conexion::Motor,
+    contar_filas,
+    db_tablas::{Contactos, Profesores},
+    destino::{Destino, DirectorioCsv, FilaGenerada, Futuro, Memoria, ScriptSql},
+    esquema::TABLAS,
+    generador::Generador,
+    migrar,
+};
+use dbdata::{Conexion, ValorColumna};
+use sqlx::Executor;
+
+use common::{generar, sqlite_en_memoria};
+
+#[tokio::test]
+async fn envia_la_misma_carga_a_varios_destinos() {
+    let directorio = std::env::temp_dir().join(format!("destino-{}", std::process::id()));
+    let script = directorio.join("respaldo.sql");
+    let csv = directorio.join("csv");
+    fs::create_dir_all(&directorio).unwrap();
+
+    let conexion = sqlite_en_memoria().await;
+    let filas = Arc::new(Memoria::new());
+    let generacion = Generador::new(conexion.clone())
+        .muestras(30)
+        .semilla(7)
+        .destino(filas.clone())
+        .destino(Arc::new(ScriptSql::crear(&script, Motor::Sqlite).unwrap()))
+        .destino(Arc::new(DirectorioCsv::crear(&csv).unwrap()))
+        .ejecutar()
+        .await
+        .unwrap();
+
+    // Los demás destinos reciben sólo las filas que la base de datos aceptó.
+    for tabla in TABLAS {
+        assert_eq!(
+            filas.cantidad(tabla) as i64,
+            contar_filas(&conexion, tabla).await.unwrap(),
+            "Filas de {tabla}"
+        );
+    }
+    let profesores = filas.filas::<Profesores>();
+    let generados: Vec<String> = generacion
+        .filas
+        .profesores
+        .iter()
+        .map(|p| format!("{p:?}"))
+        .collect();
+    assert!(profesores
+        .iter()
+        .all(|p| generados.contains(&format!("{p:?}"))));
+
+    let profesores_csv = fs::read_to_string(csv.join("Profesores.csv")).unwrap();
+    let mut lineas = profesores_csv.lines();
+    assert!(lineas.next().unwrap().starts_with("dni,"));
+    assert_eq!(lineas.count(), profesores.len());
+
+    // El script carga las mismas filas que la carga original.
+    let copia = sqlite_en_memoria().await;
+    migrar(&copia).await.unwrap();
+    let Conexion::Sqlite(pool) = &copia else {
+        unreachable!()
+    };
+    pool.execute(fs::read_to_string(&script).unwrap().as_str())
+        .await
+        .unwrap();
+    for tabla in TABLAS {
+        assert_eq!(
+            contar_filas(&copia, tabla).await.unwrap(),
+            contar_filas(&conexion, tabla).await.unwrap(),
+            "Filas de {tabla}"
+        );
+    }
+
+    fs::remove_dir_all(&directorio).unwrap();
+}
+
+/// Destino que rechaza uno de cada dos profesores y guarda el resto de las filas.
+#[derive(Default)]
+struct RechazaProfesores {
+    memoria: Memoria,
+    profesores: AtomicUsize,
+}
+
+impl Destino for RechazaProfesores {
+    fn enviar<'a>(&'a self, fila: &'a dyn FilaGenerada) -> Futuro<'a, bool> {
+        if fila.tabla() == "Profesores" && self.profesores.fetch_add(1, Ordering::Relaxed) % 2 == 1
+        {
+            return Box::pin(async { Ok(false) });
+        }
+        self.memoria.enviar(fila)
+    }
+}
+
+#[tokio::test]
+async fn no_usa_las_filas_rechazadas_como_referencia() {
+    let destino = RechazaProfesores::default();
+    let generados = generar(&destino, 40, 9).await;
+
+    assert_eq!(generados.profesores.len(), 20);
+    assert_eq!(destino.memoria.cantidad("Profesores"), 20);
+    let dnis: Vec<_> = generados.profesores.iter().map(|p| p.dni.valor()).collect();
+    let contactos = destino.memoria.filas::<Contactos>();
+    assert!(!contactos.is_empty());
+    for contacto in contactos {
+        assert!(
+            dnis.contains(&contacto.dni_profesor.valor()),
+            "{contacto:?}"
+        );
+    }
+}
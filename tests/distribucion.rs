@@ -0,0 +1,19 @@
+// Autor: Acosta Quintana, Lautaro
+use carga_datos::distribucion::Distribucion;
+
+#[test]
+fn rechaza_parametros_no_finitos() {
+    assert_eq!(
+        "zipf:1.2".parse::<Distribucion>(),
+        Ok(Distribucion::Zipf { exponente: 1.2 })
+    );
+    for s in [
+        "zipf:NaN",
+        "zipf:inf",
+        "normal:NaN,1",
+        "normal:2,inf",
+        "normal:inf,1",
+    ] {
+        assert!(s.parse::<Distribucion>().is_err(), "{s}");
+    }
+}
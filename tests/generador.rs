@@ -0,0 +1,56 @@
+// Autor: Acosta Quintana, Lautaro
+mod common;
+
+use carga_datos::{contar_filas, generador::Generador, guardia::BaseRechazada};
+
+use common::sqlite_en_memoria;
+
+#[tokio::test]
+async fn la_misma_semilla_genera_las_mismas_filas() {
+    let primera = sqlite_en_memoria().await;
+    let generacion = Generador::new(primera.clone())
+        .muestras(40)
+        .semilla(42)
+        .cantidad("Profesores", 25)
+        .ejecutar()
+        .await
+        .unwrap();
+    assert_eq!(generacion.semilla, 42);
+    assert_eq!(generacion.filas.profesores.len(), 25);
+    assert_eq!(contar_filas(&primera, "Profesores").await.unwrap(), 25);
+    assert_eq!(generacion.filas.direcciones.len(), 40);
+    let cargadas = |tabla: &str| {
+        generacion
+            .conteos
+            .iter()
+            .find(|c| c.tabla == tabla)
+            .map_or(0, |c| c.cargadas)
+    };
+    assert_eq!(cargadas("Profesores"), 25);
+
+    let otra = Generador::new(sqlite_en_memoria().await)
+        .muestras(40)
+        .semilla(42)
+        .cantidad("Profesores", 25)
+        .ejecutar()
+        .await
+        .unwrap();
+    assert_eq!(
+        format!("{:?}", generacion.filas),
+        format!("{:?}", otra.filas)
+    );
+
+    // La base de datos ya tiene filas, por lo que se rechaza sin forzar.
+    let err = Generador::new(primera).ejecutar().await.unwrap_err();
+    assert!(err.is::<BaseRechazada>());
+}
+
+#[tokio::test]
+async fn rechaza_cantidades_de_tablas_derivadas() {
+    let err = Generador::new(sqlite_en_memoria().await)
+        .cantidad("Publico", 10)
+        .ejecutar()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Publico"));
+}
@@ -0,0 +1,238 @@
+// Autor: Acosta Quintana, Lautaro
+mod common;
+
+use std::{collections::HashMap, sync::Mutex};
+
+use carga_datos::{
+    destino::{Destino, FilaGenerada, Futuro},
+    esquema::{columnas, Columna},
+};
+use dbdata::{TipoColumna, Valor};
+use proptest::prelude::*;
+use time::Time;
+
+use common::generar;
+
+/// Las cargas usan un generador global, por lo que se ejecutan de a una.
+static CARGA: Mutex<()> = Mutex::new(());
+
+/// Hora de inicio y de fin de un horario.
+type Intervalo = (Time, Time);
+
+/// Destino que revisa que cada fila cumpla con la migración de MySQL en lugar de guardarla.
+struct Revisor {
+    esquema: Mutex<HashMap<&'static str, Vec<Columna>>>,
+    /// Horarios ya revisados de cada declaración en cada día.
+    horarios: Mutex<HashMap<(i64, String), Vec<Intervalo>>>,
+    problemas: Mutex<Vec<String>>,
+}
+
+impl Revisor {
+    fn revisar(&self, fila: &dyn FilaGenerada) -> Vec<String> {
+        let tabla = fila.tabla();
+        let esquema = self
+            .esquema
+            .lock()
+            .unwrap()
+            .entry(tabla)
+            .or_insert_with(|| columnas(tabla))
+            .clone();
+        let valores: Vec<(&str, Valor)> = fila
+            .columnas()
+            .iter()
+            .copied()
+            .zip(fila.valores())
+            .collect();
+        let valor = |columna: &str| {
+            valores
+                .iter()
+                .find(|(c, _)| *c == columna)
+                .map(|(_, v)| v.clone())
+        };
+        let mut problemas = Vec::new();
+
+        for ((columna, valor), declarado) in valores.iter().zip(fila.tipos()) {
+            let Some(tipo) = esquema
+                .iter()
+                .find(|c| c.nombre.eq_ignore_ascii_case(columna))
+                .map(|c| c.tipo.as_str())
+            else {
+                problemas.push(format!("{tabla}.{columna} no está en la migración"));
+                continue;
+            };
+            let esperado = tipo_columna(tipo);
+            if *declarado != esperado {
+                problemas.push(format!(
+                    "{tabla}.{columna} declara {declarado:?} pero en la migración es {tipo}"
+                ));
+            }
+            if let TipoColumna::Decimal(precision, escala) = esperado {
+                let numero = match valor {
+                    Valor::Decimal(n) => Some(n.to_string()),
+                    Valor::Real(n) => Some(n.to_string()),
+                    _ => None,
+                };
+                if let Some(numero) = numero {
+                    let (entero, fraccion) = numero.split_once('.').unwrap_or((&numero, ""));
+                    let enteros = entero.trim_start_matches('-').trim_start_matches('0').len();
+                    let decimales = fraccion.trim_end_matches('0').len();
+                    if enteros > (precision - escala) as usize || decimales > escala as usize {
+                        problemas.push(format!("{tabla}.{columna} = {numero} no entra en {tipo}"));
+                    }
+                }
+            }
+            match valor {
+                Valor::Texto(texto) => {
+                    if let Some(opciones) = tipo.strip_prefix("enum(") {
+                        let permitido = opciones
+                            .trim_end_matches(')')
+                            .split(',')
+                            .any(|o| o.trim().trim_matches('\'') == texto.to_lowercase());
+                        if !permitido {
+                            problemas
+                                .push(format!("{tabla}.{columna} = {texto:?} no es de {tipo}"));
+                        }
+                    }
+                    let largo = tipo
+                        .strip_prefix("varchar(")
+                        .or_else(|| tipo.strip_prefix("char("))
+                        .and_then(|l| l.trim_end_matches(')').parse::<usize>().ok());
+                    if let Some(largo) = largo {
+                        if texto.chars().count() > largo {
+                            problemas.push(format!("{tabla}.{columna} = {texto:?} excede {tipo}"));
+                        }
+                    }
+                    let digitos = match *columna {
+                        "dni" | "dniprofesor" | "dnifamiliar" => Some(8),
+                        "cuit" | "cuil" | "cuitempleador" => Some(11),
+                        _ => None,
+                    };
+                    if let Some(digitos) = digitos {
+                        if texto.len() != digitos || !texto.bytes().all(|b| b.is_ascii_digit()) {
+                            problemas.push(format!(
+                                "{tabla}.{columna} = {texto:?} no tiene {digitos} dígitos"
+                            ));
+                        }
+                    }
+                }
+                Valor::Entero(n) if tipo == "year" && !(1901..=2155).contains(n) => {
+                    problemas.push(format!("{tabla}.{columna} = {n} no es un año de MySQL"));
+                }
+                // Todas las columnas int de la migración son unsigned.
+                Valor::Entero(n) if *n < 0 && tipo == "int" => {
+                    problemas.push(format!("{tabla}.{columna} = {n} es negativo"));
+                }
+                _ => {}
+            }
+        }
+
+        if let (Some(Valor::Fecha(desde)), Some(Valor::Fecha(hasta))) =
+            (valor("desde"), valor("hasta"))
+        {
+            if desde > hasta {
+                problemas.push(format!(
+                    "{tabla}: desde {desde} es posterior a hasta {hasta}"
+                ));
+            }
+        }
+
+        if let (Some(Valor::Hora(inicio)), Some(Valor::Hora(fin))) =
+            (valor("horainicio"), valor("horafin"))
+        {
+            if fin <= inicio {
+                problemas.push(format!(
+                    "{tabla}: HoraFin {fin} no es posterior a HoraInicio {inicio}"
+                ));
+            }
+            if let (Some(Valor::Entero(declaracion)), Some(Valor::Texto(dia))) =
+                (valor("iddeclaracion"), valor("dia"))
+            {
+                let mut horarios = self.horarios.lock().unwrap();
+                let ocupados = horarios.entry((declaracion, dia.clone())).or_default();
+                if let Some((a, b)) = ocupados.iter().find(|&&(a, b)| inicio < b && a < fin) {
+                    problemas.push(format!(
+                        "{tabla}: {inicio}-{fin} se superpone con {a}-{b} el {dia} en la declaración {declaracion}"
+                    ));
+                }
+                ocupados.push((inicio, fin));
+            }
+        }
+
+        if tabla == "Contactos" {
+            let cumple = match valor("medio") {
+                Some(Valor::Texto(medio)) if medio == "Celular" || medio == "Telefono" => {
+                    !matches!(valor("numero"), Some(Valor::Nulo) | None)
+                }
+                Some(Valor::Texto(medio)) if medio == "Email" => {
+                    !matches!(valor("direccion"), Some(Valor::Nulo) | None)
+                }
+                _ => false,
+            };
+            if !cumple {
+                problemas.push(format!("Contactos no cumple el CHECK: {valores:?}"));
+            }
+        }
+        problemas
+    }
+}
+
+impl Destino for Revisor {
+    fn enviar<'a>(&'a self, fila: &'a dyn FilaGenerada) -> Futuro<'a, bool> {
+        let problemas = self.revisar(fila);
+        let aceptada = problemas.is_empty();
+        self.problemas.lock().unwrap().extend(problemas);
+        Box::pin(async move { Ok(aceptada) })
+    }
+}
+
+/// Tipo de una columna de la migración de MySQL, donde `numeric` es `decimal(10, 0)`.
+fn tipo_columna(tipo: &str) -> TipoColumna {
+    let argumentos = |prefijo: &str| {
+        tipo.strip_prefix(prefijo).map(|resto| {
+            resto
+                .trim_start_matches('(')
+                .trim_end_matches(')')
+                .split(',')
+                .filter_map(|n| n.trim().parse::<u32>().ok())
+                .collect::<Vec<_>>()
+        })
+    };
+    if let Some(&[largo]) = argumentos("varchar(")
+        .or_else(|| argumentos("char("))
+        .as_deref()
+    {
+        TipoColumna::Texto(largo as usize)
+    } else if let Some(digitos) = argumentos("decimal").or_else(|| argumentos("numeric")) {
+        TipoColumna::Decimal(
+            digitos.first().copied().unwrap_or(10),
+            digitos.get(1).copied().unwrap_or(0),
+        )
+    } else {
+        TipoColumna::Libre
+    }
+}
+
+/// Genera todas las tablas con `semilla` y devuelve los problemas de las filas que no cumplen con
+/// la migración.
+fn problemas(semilla: u64) -> Vec<String> {
+    let _carga = CARGA.lock().unwrap_or_else(|e| e.into_inner());
+    let revisor = Revisor {
+        esquema: Mutex::new(HashMap::new()),
+        horarios: Mutex::new(HashMap::new()),
+        problemas: Mutex::new(Vec::new()),
+    };
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(generar(&revisor, 50, semilla));
+    revisor.problemas.into_inner().unwrap()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn las_filas_cumplen_con_la_migracion(semilla in any::<u64>()) {
+        let problemas = problemas(semilla);
+        prop_assert!(problemas.is_empty(), "{}", problemas.join("\n"));
+    }
+}
@@ -0,0 +1,84 @@
+// Autor: Acosta Quintana, Lautaro
+mod common;
+
+use carga_datos::{
+    db_tablas::Idiomas,
+    guardia::{coincide, inspeccionar, marcar, EstadoCarga, BASES_PERMITIDAS, TABLA_MARCA},
+    migrar,
+};
+use dbdata::Conexion;
+
+use common::sqlite_en_memoria;
+
+#[test]
+fn coincide_con_los_patrones() {
+    assert!(coincide("tpi*", "TPI_2024"));
+    assert!(coincide("*test*", "carga_test"));
+    assert!(coincide("a*b*c", "abbc"));
+    assert!(!coincide("a*a", "a"));
+    assert!(!coincide("tpi*", "produccion"));
+    assert!(!coincide(":memory:", "memoria"));
+}
+
+#[tokio::test]
+async fn rechaza_bases_de_datos_con_filas() {
+    let conexion = sqlite_en_memoria().await;
+    let permitidas: Vec<String> = BASES_PERMITIDAS.iter().map(|p| p.to_string()).collect();
+
+    let inspeccion = inspeccionar(&conexion).await.unwrap();
+    assert_eq!(inspeccion.nombre, ":memory:");
+    assert_eq!(inspeccion.migraciones_pendientes, 1);
+    assert!(inspeccion.problemas(&permitidas).is_empty());
+
+    migrar(&conexion).await.unwrap();
+    let marca = marcar(&conexion, 50).await.unwrap();
+    marca
+        .actualizar(&conexion, EstadoCarga::Interrumpida, Some("Idiomas"))
+        .await
+        .unwrap();
+    let inspeccion = inspeccionar(&conexion).await.unwrap();
+    assert!(inspeccion.marcada);
+    let carga = inspeccion.ultima_carga.as_ref().unwrap();
+    assert_eq!(carga.estado, "interrumpida");
+    assert_eq!(carga.ultima_tabla.as_deref(), Some("Idiomas"));
+    assert_eq!(inspeccion.migraciones_pendientes, 0);
+    assert!(inspeccion.problemas(&[]).is_empty());
+
+    conexion.insertar(&Idiomas::new("Wichí")).await.unwrap();
+    let inspeccion = inspeccionar(&conexion).await.unwrap();
+    assert_eq!(inspeccion.filas, vec![("Idiomas".to_string(), 1)]);
+    assert_eq!(inspeccion.problemas(&permitidas).len(), 1);
+}
+
+#[tokio::test]
+async fn cada_carga_actualiza_su_marca() {
+    let conexion = sqlite_en_memoria().await;
+    let primera = marcar(&conexion, 10).await.unwrap();
+    let segunda = marcar(&conexion, 20).await.unwrap();
+    primera
+        .actualizar(&conexion, EstadoCarga::Completa, None)
+        .await
+        .unwrap();
+    segunda
+        .actualizar(&conexion, EstadoCarga::Fallida, Some("Titulos"))
+        .await
+        .unwrap();
+
+    let Conexion::Sqlite(pool) = &conexion else {
+        unreachable!();
+    };
+    let mut marcas: Vec<(i64, String, Option<String>)> = sqlx::query_as(&format!(
+        "SELECT Muestras, Estado, UltimaTabla FROM {TABLA_MARCA}"
+    ))
+    .fetch_all(pool)
+    .await
+    .unwrap();
+    marcas.sort();
+    assert_eq!(
+        marcas,
+        vec![
+            (10, "completa".to_string(), None),
+            (20, "fallida".to_string(), Some("Titulos".to_string())),
+        ]
+    );
+}
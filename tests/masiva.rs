@@ -0,0 +1,78 @@
+// Autor: Acosta Quintana, Lautaro
+mod common;
+
+use std::str::FromStr;
+
+use carga_datos::{
+    conexion::{ConfigConexion, ModoTls},
+    contar_filas,
+    db_tablas::Idiomas,
+    esquema::TABLAS,
+    masiva::{activar_carga_masiva, ConexionInfile},
+    migrar, CONTADOR,
+};
+use dbdata::{Fila, FilaTsv, ValorTsv};
+use sqlx::types::BigDecimal;
+use time::{Date, Month};
+
+use common::{generar, sqlite_en_memoria};
+
+fn tsv<T: ValorTsv>(valor: T) -> String {
+    let mut salida = String::new();
+    valor.escribir_tsv(&mut salida);
+    salida
+}
+
+#[test]
+fn escribe_los_valores_como_load_data() {
+    assert_eq!(tsv(None::<u32>), "\\N");
+    assert_eq!(tsv(Some(b'B')), "66");
+    assert_eq!(tsv(true), "1");
+    assert_eq!(
+        tsv(Date::from_calendar_date(2024, Month::March, 9).unwrap()),
+        "2024-03-09"
+    );
+    assert_eq!(tsv(BigDecimal::from_str("1234.50").unwrap()), "1234.50");
+
+    let mut fila = String::new();
+    Idiomas::new("a\tb\\c\nd").escribir_tsv(&mut fila);
+    assert_eq!(fila, "a\\tb\\\\c\\nd\n");
+    assert_eq!(Idiomas::TABLA, "Idiomas");
+    assert_eq!(Idiomas::COLUMNAS, ["nombre"]);
+}
+
+#[tokio::test]
+async fn carga_en_lotes_y_cuenta_las_filas() {
+    let conexion = sqlite_en_memoria().await;
+    migrar(&conexion).await.unwrap();
+    activar_carga_masiva(None).await;
+
+    generar(&conexion, 50, 9).await;
+
+    let conteos = CONTADOR.lock().await.get_por_tabla();
+    for tabla in TABLAS {
+        let cargadas = conteos
+            .iter()
+            .find(|c| c.tabla == tabla)
+            .map_or(0, |c| c.cargadas);
+        let filas = contar_filas(&conexion, tabla).await.unwrap();
+        assert_eq!(filas as usize, cargadas, "Filas de {tabla}");
+    }
+    assert_eq!(contar_filas(&conexion, "Profesores").await.unwrap(), 50);
+}
+
+#[test]
+fn no_usa_infile_si_se_pide_cifrar() {
+    let config = |tls| ConfigConexion {
+        url: Some("mysql://usuario@localhost/tpi".to_string()),
+        tls,
+        ..Default::default()
+    };
+    assert!(ConexionInfile::new(&config(None)).unwrap().is_some());
+    assert!(ConexionInfile::new(&config(Some(ModoTls::Deshabilitado)))
+        .unwrap()
+        .is_some());
+    for tls in [ModoTls::Preferido, ModoTls::Requerido, ModoTls::VerificarCa] {
+        assert!(ConexionInfile::new(&config(Some(tls))).unwrap().is_none());
+    }
+}
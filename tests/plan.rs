@@ -0,0 +1,40 @@
+// Autor: Acosta Quintana, Lautaro
+use carga_datos::{
+    cardinalidad::parsear_cardinalidad,
+    carga::OpcionesCarga,
+    esquema::{columnas, TABLAS},
+    plan::{bytes_de_tipo, planificar},
+};
+
+#[test]
+fn lee_los_tipos_de_la_migracion() {
+    let tipos: Vec<String> = columnas("Publico").into_iter().map(|c| c.tipo).collect();
+    assert_eq!(tipos, ["int", "char(8)"]);
+    assert_eq!(bytes_de_tipo("varchar(100)"), 101);
+    assert_eq!(bytes_de_tipo("decimal(8)"), 4);
+    assert!(TABLAS.iter().all(|t| !columnas(t).is_empty()));
+}
+
+#[test]
+fn estima_las_filas_derivadas() {
+    let mut opciones = OpcionesCarga {
+        muestras: 1000,
+        ..Default::default()
+    };
+    opciones
+        .cardinalidades
+        .configurar(parsear_cardinalidad("Profesores->Familiares:1..1").unwrap());
+    let plan = planificar(&opciones, 50, 107, 97);
+    let filas = |tabla: &str| plan.iter().find(|t| t.tabla == tabla).unwrap().filas;
+
+    assert_eq!(plan.len(), 37);
+    assert_eq!(filas("Instituciones").esperado, 50);
+    assert_eq!(filas("Titulos").esperado, 107);
+    assert_eq!(filas("Beneficia").minimo, 500);
+    assert_eq!(filas("Beneficia").maximo, 999);
+    assert_eq!(filas("Familiares").maximo, 1000);
+    // Con una distribución uniforme cerca del 37% de los profesores no tiene declaraciones
+    // juradas y se le agrega una para cumplir la cardinalidad.
+    let declaraciones = filas("DeclaracionesJuradas").esperado;
+    assert!((1350..1390).contains(&declaraciones), "{declaraciones}");
+}
@@ -0,0 +1,47 @@
+// Autor: Acosta Quintana, Lautaro
+use carga_datos::{
+    contar_filas,
+    prueba::{self, MUESTRAS},
+};
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = false)]
+async fn carga_filas_conocidas(pool: SqlitePool) -> anyhow::Result<()> {
+    let datos = prueba::cargar(pool.clone()).await?;
+    let conexion = pool.clone().into();
+    assert_eq!(datos.profesores.len(), MUESTRAS);
+    assert_eq!(
+        datos.profesores.len() as i64,
+        contar_filas(&conexion, "Profesores").await?
+    );
+    assert_eq!(
+        datos.publico.len() as i64,
+        contar_filas(&conexion, "Publico").await?
+    );
+
+    let profesor = &datos.profesores[0];
+    let nombre: String = sqlx::query_scalar("SELECT nombre FROM Profesores WHERE dni = ?")
+        .bind(&profesor.dni)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(nombre, profesor.nombre);
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn genera_siempre_los_mismos_datos(pool: SqlitePool) -> anyhow::Result<()> {
+    let datos = prueba::cargar_con(pool, |g| g.cantidad("Profesores", 5)).await?;
+    assert_eq!(datos.profesores.len(), 5);
+
+    let otra = SqlitePool::connect("sqlite::memory:").await?;
+    let otros = prueba::cargar_con(otra, |g| g.cantidad("Profesores", 5)).await?;
+    assert_eq!(
+        format!("{:?}", datos.profesores),
+        format!("{:?}", otros.profesores)
+    );
+    assert_eq!(
+        format!("{:?}", datos.publicaciones),
+        format!("{:?}", otros.publicaciones)
+    );
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// Autor: Acosta Quintana, Lautaro
+mod common;
+
+use std::collections::{HashMap, HashSet};
+
+use carga_datos::{
+    carga::MAX_AUTORES,
+    db_tablas::{Direcciones, Empleadores, Profesores, Publicaciones, Publico},
+    destino::Memoria,
+};
+use dbdata::{Fila, Valor, ValorColumna};
+
+use common::generar;
+
+#[tokio::test]
+async fn los_autores_son_los_profesores_de_publico() {
+    let memoria = Memoria::new();
+    let generados = generar(&memoria, 50, 9).await;
+
+    let profesores: HashMap<String, String> = generados
+        .profesores
+        .iter()
+        .map(|p| {
+            let Valor::Texto(dni) = p.dni.valor() else {
+                panic!("{p:?}");
+            };
+            (dni, format!("{}, {}", p.apellido, p.nombre))
+        })
+        .collect();
+    let mut autores: HashMap<i64, Vec<String>> = HashMap::new();
+    let mut pares = HashSet::new();
+    for fila in memoria.filas::<Publico>() {
+        let [Valor::Entero(publicacion), Valor::Texto(dni)] = &fila.valores()[..] else {
+            panic!("{fila:?}");
+        };
+        assert!(pares.insert((*publicacion, dni.clone())), "{fila:?}");
+        autores
+            .entry(*publicacion)
+            .or_default()
+            .push(profesores[dni].clone());
+    }
+
+    for publicacion in &generados.publicaciones {
+        let nombres = &autores[&(publicacion.id_publicacion as i64)];
+        assert!(
+            (1..=MAX_AUTORES).contains(&nombres.len()),
+            "{publicacion:?}"
+        );
+        // Los profesores encabezan la lista, antes de los coautores externos.
+        assert!(
+            publicacion.autores.starts_with(&nombres.join("; ")),
+            "{publicacion:?}: {nombres:?}"
+        );
+    }
+}
+
+#[test]
+fn omite_los_profesores_que_no_entran_en_autores() {
+    let direccion = Direcciones::new("Tucumán", "San Miguel de Tucumán", "Lamadrid");
+    let empleador = Empleadores::new(&direccion);
+    let largo = |mut p: Profesores, letra: &str| {
+        p.apellido = letra.repeat(100);
+        p.nombre = letra.repeat(80);
+        p
+    };
+    let primero = largo(Profesores::new(&empleador), "A");
+    let segundo = largo(Profesores::new(&empleador), "B");
+    let autores = [&primero, &segundo];
+
+    assert_eq!(Publicaciones::autores_que_entran(&autores), 1);
+    let publicacion = Publicaciones::new(&autores);
+    assert_eq!(
+        publicacion.autores,
+        format!("{}, {}", primero.apellido, primero.nombre)
+    );
+}
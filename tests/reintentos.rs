@@ -0,0 +1,68 @@
+// Autor: Acosta Quintana, Lautaro
+use std::time::Duration;
+
+use carga_datos::{
+    db_tablas::Idiomas,
+    insertar, migrar,
+    reintentos::{configurar_reintentos, es_transitorio, Reintentos},
+    CONTADOR,
+};
+use dbdata::Conexion;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+#[test]
+fn clasifica_los_errores() {
+    assert!(es_transitorio(&sqlx::Error::PoolTimedOut.into()));
+    assert!(!es_transitorio(&sqlx::Error::RowNotFound.into()));
+    assert!(!es_transitorio(&anyhow::anyhow!("otro error")));
+
+    let reintentos = Reintentos::default();
+    let mut rng = rand::thread_rng();
+    let primera = reintentos.espera(0, &mut rng);
+    assert!(primera >= Duration::from_millis(50) && primera <= Duration::from_millis(100));
+    assert!(reintentos.espera(30, &mut rng) <= reintentos.espera_maxima);
+}
+
+#[tokio::test]
+async fn reintenta_las_filas_bloqueadas_y_no_las_duplicadas() {
+    let archivo = std::env::temp_dir().join(format!("reintentos_{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&archivo);
+    // Sin busy_timeout, SQLite devuelve SQLITE_BUSY apenas encuentra la base de datos bloqueada.
+    let opciones = SqliteConnectOptions::new()
+        .filename(&archivo)
+        .create_if_missing(true)
+        .busy_timeout(Duration::ZERO);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect_with(opciones)
+        .await
+        .unwrap();
+    let conexion = Conexion::Sqlite(pool.clone());
+    migrar(&conexion).await.unwrap();
+    configurar_reintentos(Reintentos {
+        maximo: 20,
+        espera_inicial: Duration::from_millis(20),
+        espera_maxima: Duration::from_millis(100),
+    });
+
+    let mut bloqueo = pool.acquire().await.unwrap();
+    sqlx::query("BEGIN IMMEDIATE")
+        .execute(&mut *bloqueo)
+        .await
+        .unwrap();
+    let liberar = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        sqlx::query("COMMIT").execute(&mut *bloqueo).await.unwrap();
+    });
+    insertar(&conexion, &Idiomas::new("Mocoví")).await.unwrap();
+    liberar.await.unwrap();
+    insertar(&conexion, &Idiomas::new("Mocoví")).await.unwrap();
+
+    let conteos = CONTADOR.lock().await.get_por_tabla();
+    let idiomas = conteos.iter().find(|c| c.tabla == "Idiomas").unwrap();
+    assert_eq!((idiomas.cargadas, idiomas.rechazadas), (1, 1));
+    assert!(idiomas.reintentos > 0);
+
+    pool.close().await;
+    let _ = std::fs::remove_file(&archivo);
+}